@@ -0,0 +1,63 @@
+//! Collects the quest files in `src/bin`, so that the `ec` binary can run every solution in process.
+//!
+//! Only with the `solutions` feature: without it the registry is empty, so that a quest that does not
+//! compile cannot break the other commands.
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap());
+    let bin_dir = manifest_dir.join("src/bin");
+    let enabled = env::var_os("CARGO_FEATURE_SOLUTIONS").is_some();
+    if enabled {
+        println!("cargo::rerun-if-changed={}", bin_dir.display());
+    } else {
+        println!("cargo::rerun-if-changed=build.rs");
+    }
+
+    let mut quests: Vec<(String, PathBuf)> = fs::read_dir(&bin_dir)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter_map(|path| {
+            let name = path.file_stem()?.to_str()?.to_string();
+            let is_quest = path.extension()? == "rs"
+                && name.starts_with("quest_")
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            is_quest.then_some((name, path))
+        })
+        .collect();
+    quests.sort();
+    if !enabled {
+        quests.clear();
+    }
+
+    // The quest tests already run as part of the quest binaries
+    let mut out = String::from("// Generated by build.rs from the quest files in src/bin\n");
+    for (name, path) in &quests {
+        writeln!(
+            out,
+            "\n#[cfg(not(test))]\n#[path = {:?}]\nmod {name};",
+            path.to_string_lossy()
+        )
+        .unwrap();
+    }
+
+    out.push_str("\n/// Every quest solution in src/bin\n#[cfg(not(test))]\n");
+    out.push_str("fn solutions() -> Vec<&'static dyn ec::ec::solution::Solution> {\n    vec![\n");
+    for (name, _) in &quests {
+        writeln!(out, "        &{name}::QuestSolution,").unwrap();
+    }
+    out.push_str("    ]\n}\n");
+    out.push_str("\n#[cfg(test)]\n");
+    out.push_str(
+        "fn solutions() -> Vec<&'static dyn ec::ec::solution::Solution> {\n    Vec::new()\n}\n",
+    );
+
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+    fs::write(out_dir.join("solutions.rs"), out).unwrap();
+}
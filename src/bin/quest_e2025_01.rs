@@ -68,26 +68,26 @@ pub fn part_three(notes: &str) -> Result<String, Box<dyn Error>> {
 mod tests {
     use super::*;
     use ec::ec::runner::Answer;
-    use ec::read_example_file;
+    use ec::{Part, read_example_file};
     use pretty_assertions::assert_eq;
 
     #[test]
     fn test_part_one() {
-        let notes = read_example_file(EVENT, QUEST, 1);
+        let notes = read_example_file(EVENT, QUEST, Part::One);
         let result = part_one(&notes).unpack().unwrap();
         assert_eq!(result, "Fyrryn");
     }
 
     #[test]
     fn test_part_two() {
-        let notes = read_example_file(EVENT, QUEST, 2);
+        let notes = read_example_file(EVENT, QUEST, Part::Two);
         let result = part_two(&notes).unpack().unwrap();
         assert_eq!(result, "Elarzris");
     }
 
     #[test]
     fn test_part_three() {
-        let notes = read_example_file(EVENT, QUEST, 3);
+        let notes = read_example_file(EVENT, QUEST, Part::Three);
         let result = part_three(&notes).unpack().unwrap();
         assert_eq!(result, "Drakzyph");
     }
@@ -0,0 +1,157 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Bytes currently allocated
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+/// Most bytes allocated at once since the last [`measure`]
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+/// Bytes allocated in total, including reallocations
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Counts the allocations of every binary linking this crate, when the `alloc-stats` feature is enabled.
+///
+/// Installed by the library rather than by [`solution!`](crate::solution), as the `ec` binary
+/// contains every quest and can only have one global allocator.
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// A global allocator that forwards to the system allocator and counts what it allocates
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn record_alloc(size: usize) {
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+        ALLOCATED.fetch_add(size, Ordering::Relaxed);
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+// SAFETY: every call is forwarded to the system allocator unchanged, only the counters are added
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    /// Counted as freeing the old block and allocating the new one
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+            Self::record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Heap usage of a part
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Most bytes allocated at once, on top of what was allocated before the part started
+    pub peak: usize,
+    /// Bytes allocated in total
+    pub allocated: usize,
+    pub allocations: usize,
+}
+
+/// A running measurement of heap usage, started by [`measure`]
+#[derive(Debug)]
+pub struct AllocMeasurement {
+    current: usize,
+    allocated: usize,
+    allocations: usize,
+}
+
+/// Starts measuring heap usage, `None` if the `alloc-stats` feature is disabled.
+///
+/// The counters are shared by all threads, so allocations of other threads are included.
+pub fn measure() -> Option<AllocMeasurement> {
+    if !cfg!(feature = "alloc-stats") {
+        return None;
+    }
+
+    let current = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(current, Ordering::Relaxed);
+    Some(AllocMeasurement {
+        current,
+        allocated: ALLOCATED.load(Ordering::Relaxed),
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+    })
+}
+
+impl AllocMeasurement {
+    pub fn finish(self) -> AllocStats {
+        AllocStats {
+            peak: PEAK.load(Ordering::Relaxed).saturating_sub(self.current),
+            allocated: ALLOCATED.load(Ordering::Relaxed) - self.allocated,
+            allocations: ALLOCATIONS.load(Ordering::Relaxed) - self.allocations,
+        }
+    }
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.50 MiB`
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.2} {}", UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hint::black_box;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.50 KiB");
+        assert_eq!(format_bytes(3 << 30), "3.00 GiB");
+    }
+
+    #[test]
+    fn test_measure() {
+        let measurement = measure();
+        if !cfg!(feature = "alloc-stats") {
+            assert!(measurement.is_none());
+            return;
+        }
+        let measurement = measurement.unwrap();
+
+        let mut v: Vec<u64> = Vec::with_capacity(1 << 17);
+        v.extend(0..1 << 17);
+        let stats = measurement.finish();
+        drop(black_box(v));
+        assert!(stats.peak >= 1 << 20, "{stats:?}");
+        assert!(stats.allocated >= 1 << 20, "{stats:?}");
+        assert!(stats.allocations >= 1, "{stats:?}");
+    }
+}
@@ -0,0 +1,198 @@
+use crate::ec::Part;
+use crate::ec::config::{OutputFormat, RunConfig};
+use crate::ec::panics::catch_panic;
+use crate::ec::runner::{ANSI_BOLD, ANSI_RESET, Answer, report_panic};
+use crate::ec::solution::{PartOutcome, PartStatus};
+use serde_json::json;
+use std::fmt::{Debug, Display};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Share of the time budget spent warming up before samples are taken
+const WARMUP_SHARE: u32 = 10;
+/// Upper bound on samples, so that very fast parts do not collect millions of them
+const MAX_SAMPLES: usize = 100_000;
+
+/// Timing statistics of the repeated runs of a part
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchStats {
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub std_dev: Duration,
+}
+
+impl BenchStats {
+    /// Computes the statistics of the given samples, `None` if there are none
+    pub fn from_samples(samples: &mut [Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort_unstable();
+
+        let n = samples.len();
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        } else {
+            samples[n / 2]
+        };
+        let mean = samples.iter().sum::<Duration>() / n as u32;
+        let variance = samples
+            .iter()
+            .map(|s| (s.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / (n.max(2) - 1) as f64;
+
+        Some(Self {
+            samples: n,
+            min: samples[0],
+            median,
+            mean,
+            std_dev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+/// Runs a part repeatedly for the configured time budget and reports its answer and timing statistics.
+///
+/// Unlike [`run_part`](crate::run_part), the solution has to be [`Fn`], as it is called many times.
+/// The duration of the outcome is the median.
+pub fn bench_part<'a, A: Answer + Sized>(
+    func: impl Fn(&'a str) -> A,
+    input: &'a str,
+    part: Part,
+    config: &RunConfig,
+) -> (PartOutcome, Option<BenchStats>)
+where
+    <A as Answer>::Output: Display,
+    <A as Answer>::Error: Debug,
+{
+    if input.is_empty() {
+        match config.format {
+            OutputFormat::Text => println!("Part {}: -", part),
+            OutputFormat::Json => println!("{}", json!({ "part": part, "answer": null })),
+        }
+        return (PartOutcome::new(part, PartStatus::Skipped), None);
+    }
+
+    // The first run also provides the answer, and a part that panics is not benchmarked
+    let timer = Instant::now();
+    let answer = match catch_panic(|| func(black_box(input))) {
+        Ok(answer) => answer.unpack().map(|a| a.to_string()),
+        Err(caught) => {
+            return (
+                report_panic(part, &caught, timer.elapsed(), None, config),
+                None,
+            );
+        }
+    };
+
+    let warmup_end = Instant::now() + config.bench_time / WARMUP_SHARE;
+    while Instant::now() < warmup_end {
+        black_box(func(black_box(input)));
+    }
+
+    let mut samples = Vec::new();
+    let end = Instant::now() + config.bench_time;
+    while samples.is_empty() || (Instant::now() < end && samples.len() < MAX_SAMPLES) {
+        let timer = Instant::now();
+        black_box(func(black_box(input)));
+        samples.push(timer.elapsed());
+    }
+    let stats = BenchStats::from_samples(&mut samples).expect("at least one sample");
+    let outcome = PartOutcome {
+        part,
+        answer: answer.as_ref().ok().cloned(),
+        expected: None,
+        duration: Some(stats.median),
+        memory: None,
+        status: match &answer {
+            Ok(_) => PartStatus::Solved,
+            Err(e) => PartStatus::Failed(format!("{e:?}")),
+        },
+    };
+
+    match config.format {
+        OutputFormat::Text => {
+            let answer = match &answer {
+                Ok(answer) if answer.contains('\n') => "(multiline)".to_string(),
+                Ok(answer) => format!("{ANSI_BOLD}{answer}{ANSI_RESET}"),
+                Err(e) => format!("- ({e:?})"),
+            };
+            println!(
+                "Part {part}: {answer} (median {:.2?} ± {:.2?} over {} runs)",
+                stats.median, stats.std_dev, stats.samples
+            );
+        }
+        OutputFormat::Json => {
+            let (answer, error) = match answer {
+                Ok(answer) => (Some(answer), None),
+                Err(e) => (None, Some(format!("{e:?}"))),
+            };
+            println!(
+                "{}",
+                json!({
+                    "part": part,
+                    "answer": answer,
+                    "error": error,
+                    "bench": {
+                        "samples": stats.samples,
+                        "min": stats.min.as_secs_f64(),
+                        "median": stats.median.as_secs_f64(),
+                        "mean": stats.mean.as_secs_f64(),
+                        "std_dev": stats.std_dev.as_secs_f64(),
+                    },
+                })
+            );
+        }
+    }
+
+    (outcome, Some(stats))
+}
+
+/// Prints a table with the statistics of every benchmarked part of a quest
+pub fn print_bench_summary(results: &[(Part, BenchStats)]) {
+    if results.is_empty() {
+        return;
+    }
+
+    println!();
+    println!(
+        "{ANSI_BOLD}Part | {:>7} | {:>10} | {:>10} | {:>10} | {:>10}{ANSI_RESET}",
+        "Runs", "Min", "Median", "Mean", "Std dev"
+    );
+    for (part, stats) in results {
+        println!(
+            "{:>4} | {:>7} | {:>10.2?} | {:>10.2?} | {:>10.2?} | {:>10.2?}",
+            part.as_u8(),
+            stats.samples,
+            stats.min,
+            stats.median,
+            stats.mean,
+            stats.std_dev
+        );
+    }
+    let total: Duration = results.iter().map(|(_, stats)| stats.median).sum();
+    println!("Total (median): {total:.2?}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_bench_stats() {
+        let mut samples = [4, 1, 3, 2].map(Duration::from_micros);
+        let stats = BenchStats::from_samples(&mut samples).unwrap();
+        assert_eq!(stats.samples, 4);
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_nanos(2500));
+        assert_eq!(stats.mean, Duration::from_nanos(2500));
+        // Sample standard deviation of 1, 2, 3, 4 µs is sqrt(5/3) µs
+        assert_eq!(stats.std_dev.as_nanos(), 1291);
+
+        assert_eq!(BenchStats::from_samples(&mut []), None);
+    }
+}
@@ -0,0 +1,60 @@
+use crate::Quest;
+use crate::ec::Event;
+use crate::ec::paths::project_root;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const CACHE_DIR: &str = ".ec-cache";
+
+/// On-disk cache for data fetched from the API, keyed by event, quest and seed
+#[derive(Debug, Clone)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Uses `EC_CACHE_DIR` if set, `.ec-cache` in the project root otherwise
+    pub fn from_env() -> Self {
+        match std::env::var_os("EC_CACHE_DIR") {
+            Some(dir) => Self::new(dir),
+            None => Self::new(project_root().join(CACHE_DIR)),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, event: Event, quest: Quest, seed: u32, name: &str) -> PathBuf {
+        self.dir
+            .join(event.to_string())
+            .join(quest.to_string())
+            .join(seed.to_string())
+            .join(name)
+    }
+
+    /// Reads a cached entry, returns `None` if it was never stored
+    pub fn read(&self, event: Event, quest: Quest, seed: u32, name: &str) -> Option<String> {
+        fs::read_to_string(self.path(event, quest, seed, name)).ok()
+    }
+
+    pub fn write(
+        &self,
+        event: Event,
+        quest: Quest,
+        seed: u32,
+        name: &str,
+        content: &str,
+    ) -> io::Result<()> {
+        let path = self.path(event, quest, seed, name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, content)
+    }
+}
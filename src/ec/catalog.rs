@@ -0,0 +1,121 @@
+use crate::ec::{Event, Quest, QuestId};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Quest count assumed for events that are not in the catalog
+const DEFAULT_EVENT_QUESTS: u8 = 20;
+/// Quest count assumed for stories that are not in the catalog
+const DEFAULT_STORY_QUESTS: u8 = 3;
+
+/// Metadata about an event or story
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventInfo {
+    pub event: Event,
+    pub quests: u8,
+    /// Seconds since the UNIX epoch at which the first quest unlocks, if known
+    #[serde(default)]
+    pub start: Option<u64>,
+}
+
+impl EventInfo {
+    fn new(event: Event, quests: u8, start: Option<u64>) -> Self {
+        Self {
+            event,
+            quests,
+            start,
+        }
+    }
+
+    /// Number used for the event/story in API and asset paths
+    pub fn api_id(&self) -> u32 {
+        self.event.as_u32()
+    }
+
+    /// Whether the first quest is already unlocked at the given time, assumed for an unknown start
+    pub fn is_open(&self, now: SystemTime) -> bool {
+        self.start
+            .is_none_or(|start| now >= UNIX_EPOCH + Duration::from_secs(start))
+    }
+
+    pub fn quests(&self) -> impl Iterator<Item = Quest> + use<> {
+        (1..=self.quests).filter_map(|q| Quest::try_from(q).ok())
+    }
+}
+
+/// Known events and stories with their quest counts
+#[derive(Debug, Clone)]
+pub struct Catalog {
+    events: Vec<EventInfo>,
+}
+
+impl Catalog {
+    /// Catalog bundled with this crate, as no known API endpoint lists the events.
+    ///
+    /// New events and stories have to be added here. They start at 23:00 UTC.
+    pub fn bundled() -> Self {
+        Self {
+            events: vec![
+                EventInfo::new(Event::Event(2024), 20, Some(1730761200)),
+                EventInfo::new(Event::Event(2025), 20, Some(1762210800)),
+                EventInfo::new(Event::Story(1), 3, Some(1743462000)),
+                EventInfo::new(Event::Story(2), 3, Some(1751324400)),
+            ],
+        }
+    }
+
+    pub fn events(&self) -> &[EventInfo] {
+        &self.events
+    }
+
+    /// Returns the info for the event, guessing the quest count for unknown events and stories
+    pub fn get(&self, event: Event) -> EventInfo {
+        self.events
+            .iter()
+            .find(|e| e.event == event)
+            .cloned()
+            .unwrap_or_else(|| {
+                let quests = match event {
+                    Event::Event(_) => DEFAULT_EVENT_QUESTS,
+                    Event::Story(_) => DEFAULT_STORY_QUESTS,
+                };
+                EventInfo::new(event, quests, None)
+            })
+    }
+
+    /// Checks that the quest exists in its event or story
+    pub fn validate(&self, id: QuestId) -> Result<(), String> {
+        let info = self.get(id.event);
+        if id.quest.as_u8() > info.quests {
+            Err(format!(
+                "{} only has {} quests, but quest {} was requested",
+                id.event,
+                info.quests,
+                id.quest.as_u8()
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_catalog() {
+        let catalog = Catalog::bundled();
+        let story = catalog.get(Event::Story(1));
+        assert_eq!(story.quests, 3);
+        assert!(!story.is_open(UNIX_EPOCH + Duration::from_secs(1743462000 - 1)));
+        assert!(story.is_open(UNIX_EPOCH + Duration::from_secs(1743462000)));
+
+        assert_eq!(catalog.get(Event::Story(9)).quests, DEFAULT_STORY_QUESTS);
+        assert!(catalog.get(Event::Story(9)).is_open(UNIX_EPOCH));
+
+        let id = |quest: u8| QuestId::new(Event::Event(2025), quest.try_into().unwrap());
+        assert!(catalog.validate(id(20)).is_ok());
+        assert!(catalog.validate(id(21)).is_err());
+    }
+}
@@ -1,12 +1,19 @@
 use crate::Quest;
 use crate::ec::Event;
+use crate::ec::Part;
+use crate::ec::cache::Cache;
+use crate::ec::paths::project_root;
 use aes::Aes256;
 use aes::cipher::BlockDecryptMut;
 use block_padding::Pkcs7;
 use cbc::{Decryptor, cipher::KeyIvInit};
+use reqwest::StatusCode;
+use reqwest::header::RETRY_AFTER;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
+use std::time::Duration;
 use thiserror::Error;
 
 type Aes256CbcDec = Decryptor<Aes256>;
@@ -17,6 +24,15 @@ const BASE_URL: &str = "https://everybody.codes";
 // See https://www.reddit.com/r/everybodycodes/comments/1p75qfr/2025_please_update_your_tools/
 const CDN_URL: &str = "https://everybody.codes";
 
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/iTitus/everybody-codes)"
+);
+
 #[derive(Error, Debug)]
 pub enum ClientError {
     #[error("session not found")]
@@ -25,8 +41,22 @@ pub enum ClientError {
     SeedNotConfigured,
     #[error("event/story not configured")]
     EventNotConfigured,
+    #[error("session is invalid or expired (HTTP {0})")]
+    SessionInvalid(u16),
+    #[error("{}", not_unlocked_message(*.event, *.quest, *.part))]
+    NotUnlocked {
+        event: Event,
+        quest: Quest,
+        part: Option<Part>,
+    },
+    #[error("rate limited by the server{}", .retry_after.map(|d| format!(", retry after {}s", d.as_secs())).unwrap_or_default())]
+    RateLimited { retry_after: Option<Duration> },
+    #[error("server error (HTTP {0})")]
+    ServerError(u16),
     #[error("HTTP error: {0}")]
     HttpError(String),
+    #[error("unexpected response from the server (HTTP {status}): {excerpt}")]
+    UnexpectedResponse { status: u16, excerpt: String },
     #[error("Decryption error: {0}")]
     DecryptionError(String),
     #[error("Reqwest error: {0}")]
@@ -35,6 +65,108 @@ pub enum ClientError {
     IoError(#[from] std::io::Error),
 }
 
+impl ClientError {
+    /// Suggests what the user can do about this error
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            Self::SessionNotFound => Some(
+                "save your everybody-codes session cookie to .ec-session in the project or home directory"
+                    .to_string(),
+            ),
+            Self::SessionInvalid(_) => Some(
+                "refresh your .ec-session cookie, it has probably expired".to_string(),
+            ),
+            Self::SeedNotConfigured => {
+                Some("set EC_SEED in .cargo/config.toml or leave it empty to fetch it".to_string())
+            }
+            Self::EventNotConfigured => {
+                Some("pass --event or set EC_EVENT in .cargo/config.toml".to_string())
+            }
+            Self::NotUnlocked {
+                part: Some(part @ (Part::Two | Part::Three)),
+                ..
+            } => Some(format!(
+                "part {part} not unlocked yet, solve part {} first",
+                part.previous().unwrap_or(Part::One)
+            )),
+            Self::NotUnlocked { .. } => {
+                Some("the quest is not unlocked yet, wait for its release".to_string())
+            }
+            Self::RateLimited {
+                retry_after: Some(retry_after),
+            } => Some(format!(
+                "wait {}s before trying again",
+                retry_after.as_secs()
+            )),
+            Self::RateLimited { retry_after: None } => {
+                Some("wait a while before trying again".to_string())
+            }
+            Self::ServerError(_) => {
+                Some("the server is having trouble, try again later".to_string())
+            }
+            _ => None,
+        }
+    }
+}
+
+fn not_unlocked_message(event: Event, quest: Quest, part: Option<Part>) -> String {
+    match part {
+        Some(part) => format!("{event}-{quest} part {part} is not unlocked yet"),
+        None => format!("{event}-{quest} is not unlocked yet"),
+    }
+}
+
+/// Maps unsuccessful responses to the matching [`ClientError`].
+///
+/// `not_found` is returned for a 404, which usually means that the requested quest or part is not available yet.
+fn check_status(
+    response: reqwest::blocking::Response,
+    not_found: impl FnOnce() -> ClientError,
+) -> Result<reqwest::blocking::Response, ClientError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    Err(match status {
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+            ClientError::SessionInvalid(status.as_u16())
+        }
+        StatusCode::NOT_FOUND => not_found(),
+        StatusCode::TOO_MANY_REQUESTS => ClientError::RateLimited {
+            retry_after: response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse().ok())
+                .map(Duration::from_secs),
+        },
+        s if s.is_server_error() => ClientError::ServerError(s.as_u16()),
+        s => ClientError::HttpError(format!("{s} for {}", response.url())),
+    })
+}
+
+/// Longest part of an unexpected response body kept for the error message
+const EXCERPT_LENGTH: usize = 100;
+
+/// An unexpected response, with a short single-line excerpt of its body instead of e.g. a whole HTML page
+fn unexpected_response(status: StatusCode, body: &str) -> ClientError {
+    let body = body.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut excerpt: String = body.chars().take(EXCERPT_LENGTH).collect();
+    if excerpt.len() < body.len() {
+        excerpt.push('…');
+    }
+    ClientError::UnexpectedResponse {
+        status: status.as_u16(),
+        excerpt,
+    }
+}
+
+const INPUT_CACHE_FILE: &str = "input.json";
+const KEYS_CACHE_FILE: &str = "keys.json";
+const DESCRIPTION_CACHE_FILE: &str = "description.json";
+
+/// Encrypted data for each part, used for both the inputs and the descriptions
 #[derive(Debug, Deserialize)]
 struct EncryptedInput {
     #[serde(rename = "1")]
@@ -45,46 +177,202 @@ struct EncryptedInput {
     part3_input: Option<String>,
 }
 
+impl EncryptedInput {
+    fn take(self, part: Part) -> Option<String> {
+        match part {
+            Part::One => self.part1_input,
+            Part::Two => self.part2_input,
+            Part::Three => self.part3_input,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct UserResponse {
     seed: u32,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
 struct QuestResponse {
-    #[serde(rename = "key1")]
+    #[serde(rename = "key1", skip_serializing_if = "Option::is_none")]
     part1_key: Option<String>,
-    #[serde(rename = "key2")]
+    #[serde(rename = "key2", skip_serializing_if = "Option::is_none")]
     part2_key: Option<String>,
-    #[serde(rename = "key3")]
+    #[serde(rename = "key3", skip_serializing_if = "Option::is_none")]
     part3_key: Option<String>,
 }
 
+impl QuestResponse {
+    fn key(&self, part: Part) -> Option<&String> {
+        match part {
+            Part::One => self.part1_key.as_ref(),
+            Part::Two => self.part2_key.as_ref(),
+            Part::Three => self.part3_key.as_ref(),
+        }
+    }
+
+    /// Keeps keys that are already known but missing from `other`
+    fn merge(self, other: Self) -> Self {
+        Self {
+            part1_key: other.part1_key.or(self.part1_key),
+            part2_key: other.part2_key.or(self.part2_key),
+            part3_key: other.part3_key.or(self.part3_key),
+        }
+    }
+}
+
 #[derive(Debug, Serialize)]
 struct AnswerPayload {
     answer: String,
 }
 
+/// Response of the API after submitting an answer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmissionResponse {
+    pub correct: bool,
+    #[serde(default)]
+    pub length_correct: bool,
+    #[serde(default)]
+    pub first_correct: bool,
+    /// Time taken to solve the part, as reported by the API
+    #[serde(default)]
+    pub time: Option<u64>,
+    #[serde(default)]
+    pub local_time: Option<u64>,
+    #[serde(default)]
+    pub global_time: Option<u64>,
+    #[serde(default)]
+    pub local_place: Option<i64>,
+    #[serde(default)]
+    pub global_place: Option<i64>,
+    #[serde(default)]
+    pub global_score: Option<i64>,
+}
+
 pub struct Client {
     session: String,
     seed: u32,
+    base_url: String,
+    cdn_url: String,
+    cache: Option<Cache>,
     http_client: reqwest::blocking::Client,
 }
 
-impl Client {
-    pub fn try_new() -> Result<Self, ClientError> {
-        let session = Self::read_session()?;
+/// Builder for [`Client`].
+///
+/// Everything that is not set explicitly is discovered the same way as in [`Client::try_new`]:
+/// the session is read from `.ec-session` in the project root or the home directory,
+/// the seed from `EC_SEED` (or fetched from the API), the URLs from `EC_BASE_URL`/`EC_CDN_URL`
+/// and the cache directory from `EC_CACHE_DIR`.
+#[derive(Debug, Default, Clone)]
+pub struct ClientBuilder {
+    session: Option<String>,
+    seed: Option<u32>,
+    base_url: Option<String>,
+    cdn_url: Option<String>,
+    timeout: Option<Duration>,
+    user_agent: Option<String>,
+    proxy: Option<reqwest::Proxy>,
+    cache: Option<Option<Cache>>,
+}
+
+impl ClientBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the session cookie instead of reading `.ec-session`
+    pub fn session(mut self, session: impl Into<String>) -> Self {
+        self.session = Some(session.into());
+        self
+    }
+
+    /// Sets the seed instead of reading `EC_SEED` or fetching it from the API
+    pub fn seed(mut self, seed: u32) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Sets the base URL of the API instead of reading `EC_BASE_URL`
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Sets the URL the encrypted inputs are fetched from instead of reading `EC_CDN_URL`
+    pub fn cdn_url(mut self, cdn_url: impl Into<String>) -> Self {
+        self.cdn_url = Some(cdn_url.into());
+        self
+    }
+
+    /// Sets the timeout for each request, the default is 30 seconds
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the `User-Agent` header sent with each request
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Routes all requests through the given proxy
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Caches encrypted inputs and keys in the given directory instead of `EC_CACHE_DIR` or `.ec-cache`
+    pub fn cache_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.cache = Some(Some(Cache::new(dir)));
+        self
+    }
+
+    /// Always fetches inputs and keys from the API
+    pub fn without_cache(mut self) -> Self {
+        self.cache = Some(None);
+        self
+    }
 
-        let http_client = reqwest::blocking::Client::builder().build()?;
+    pub fn build(self) -> Result<Client, ClientError> {
+        let session = match self.session {
+            Some(session) => session,
+            None => Client::read_session()?,
+        };
+
+        let base_url = self
+            .base_url
+            .or_else(|| std::env::var("EC_BASE_URL").ok())
+            .map(|url| url.trim_end_matches('/').to_string());
+        let cdn_url = self
+            .cdn_url
+            .or_else(|| std::env::var("EC_CDN_URL").ok())
+            .map(|url| url.trim_end_matches('/').to_string())
+            .or_else(|| base_url.clone())
+            .unwrap_or_else(|| CDN_URL.to_string());
+        let base_url = base_url.unwrap_or_else(|| BASE_URL.to_string());
+
+        let mut http_client = reqwest::blocking::Client::builder()
+            .user_agent(self.user_agent.as_deref().unwrap_or(USER_AGENT))
+            .timeout(self.timeout.unwrap_or(DEFAULT_TIMEOUT));
+        if let Some(proxy) = self.proxy {
+            http_client = http_client.proxy(proxy);
+        }
+        let http_client = http_client.build()?;
 
         let mut client = Client {
             session,
             seed: 0, // Temporary value
+            base_url,
+            cdn_url,
+            cache: self.cache.unwrap_or_else(|| Some(Cache::from_env())),
             http_client,
         };
 
         // Check if seed needs to be fetched
-        client.seed = match Self::get_seed() {
+        client.seed = match self.seed.map_or_else(Client::seed_from_env, Ok) {
             Ok(s) => s,
             Err(_) => {
                 // Seed not configured or empty, fetch it from API
@@ -98,10 +386,20 @@ impl Client {
 
         Ok(client)
     }
+}
+
+impl Client {
+    pub fn try_new() -> Result<Self, ClientError> {
+        Self::builder().build()
+    }
+
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
 
     fn read_session() -> Result<String, ClientError> {
         for dir in [
-            PathBuf::new(),
+            project_root(),
             std::env::home_dir().ok_or(ClientError::SessionNotFound)?,
         ] {
             let session_path = dir.join(".ec-session");
@@ -115,7 +413,8 @@ impl Client {
         Err(ClientError::SessionNotFound)
     }
 
-    fn get_seed() -> Result<u32, ClientError> {
+    /// The seed configured in `EC_SEED`, without fetching it from the API
+    pub fn seed_from_env() -> Result<u32, ClientError> {
         let seed_str = std::env::var("EC_SEED").map_err(|_| ClientError::SeedNotConfigured)?;
         let seed_str = seed_str.trim();
 
@@ -128,13 +427,15 @@ impl Client {
     }
 
     pub fn fetch_user_seed(&self) -> Result<u32, ClientError> {
-        let url = format!("{BASE_URL}/api/user/me");
+        let url = format!("{}/api/user/me", self.base_url);
         let response = self
             .http_client
             .get(&url)
             .header("Cookie", format!("everybody-codes={}", self.session))
-            .send()?
-            .error_for_status()?;
+            .send()?;
+        let response = check_status(response, || {
+            ClientError::HttpError(format!("404 Not Found for {url}"))
+        })?;
 
         let user: UserResponse = response
             .json()
@@ -143,40 +444,63 @@ impl Client {
         Ok(user.seed)
     }
 
+    /// Fetches the encrypted input of a part, using the cache if the input was downloaded before
     pub fn fetch_encrypted_input(
         &self,
         event: Event,
         quest: Quest,
-        part: u8,
+        part: Part,
     ) -> Result<String, ClientError> {
-        let url = format!(
-            "{CDN_URL}/assets/{}/{}/input/{}.json",
-            event.as_u32(),
-            quest.as_u8(),
-            self.seed
-        );
-
-        let response = self.http_client.get(&url).send()?.error_for_status()?;
-        let inputs: EncryptedInput = response.json()?;
-
-        let encrypted = match part {
-            1 => inputs.part1_input,
-            2 => inputs.part2_input,
-            3 => inputs.part3_input,
-            _ => None,
+        let inputs: EncryptedInput = match self.read_cache(event, quest, INPUT_CACHE_FILE) {
+            Some(inputs) => inputs,
+            None => {
+                let url = format!(
+                    "{}/assets/{}/{}/input/{}.json",
+                    self.cdn_url,
+                    event.as_u32(),
+                    quest.as_u8(),
+                    self.seed
+                );
+
+                let response = self.http_client.get(&url).send()?;
+                let response = check_status(response, || ClientError::NotUnlocked {
+                    event,
+                    quest,
+                    part: None,
+                })?;
+                let status = response.status();
+                let body = response.text()?;
+                let inputs =
+                    serde_json::from_str(&body).map_err(|_| unexpected_response(status, &body))?;
+                self.write_cache(event, quest, INPUT_CACHE_FILE, &body);
+                inputs
+            }
         };
-        encrypted
-            .ok_or_else(|| ClientError::HttpError(format!("Part {part} not found in response")))
+
+        inputs.take(part).ok_or(ClientError::NotUnlocked {
+            event,
+            quest,
+            part: Some(part),
+        })
     }
 
+    /// Fetches the key of a part, only asking the API if the key is not cached yet
     pub fn fetch_decryption_key(
         &self,
         event: Event,
         quest: Quest,
-        part: u8,
+        part: Part,
     ) -> Result<String, ClientError> {
+        let cached: QuestResponse = self
+            .read_cache(event, quest, KEYS_CACHE_FILE)
+            .unwrap_or_default();
+        if let Some(key) = cached.key(part) {
+            return Ok(key.clone());
+        }
+
         let url = format!(
-            "{BASE_URL}/api/event/{}/quest/{}",
+            "{}/api/event/{}/quest/{}",
+            self.base_url,
             event.as_u32(),
             quest.as_u8()
         );
@@ -185,25 +509,95 @@ impl Client {
             .http_client
             .get(&url)
             .header("Cookie", format!("everybody-codes={}", self.session))
-            .send()?
-            .error_for_status()?;
-
-        let quest_data: QuestResponse = response.json()?;
+            .send()?;
+        let response = check_status(response, || ClientError::NotUnlocked {
+            event,
+            quest,
+            part: None,
+        })?;
+
+        let quest_data = cached.merge(response.json()?);
+        match serde_json::to_string(&quest_data) {
+            Ok(content) => self.write_cache(event, quest, KEYS_CACHE_FILE, &content),
+            Err(e) => eprintln!("Could not cache the keys: {e}"),
+        }
 
-        let key = match part {
-            1 => quest_data.part1_key,
-            2 => quest_data.part2_key,
-            3 => quest_data.part3_key,
-            _ => None,
-        };
+        quest_data
+            .key(part)
+            .cloned()
+            .ok_or(ClientError::NotUnlocked {
+                event,
+                quest,
+                part: Some(part),
+            })
+    }
 
-        key.ok_or_else(|| {
-            ClientError::HttpError(format!(
-                "Key for part {part} not available (possibly not solved yet)"
-            ))
+    /// Fetches the encrypted description of all parts, using the cache if it was downloaded before
+    fn fetch_encrypted_description(
+        &self,
+        event: Event,
+        quest: Quest,
+        part: Part,
+    ) -> Result<String, ClientError> {
+        let descriptions: EncryptedInput =
+            match self.read_cache(event, quest, DESCRIPTION_CACHE_FILE) {
+                Some(descriptions) => descriptions,
+                None => {
+                    let url = format!(
+                        "{}/assets/{}/{}/description.json",
+                        self.cdn_url,
+                        event.as_u32(),
+                        quest.as_u8()
+                    );
+
+                    let response = self.http_client.get(&url).send()?;
+                    let response = check_status(response, || ClientError::NotUnlocked {
+                        event,
+                        quest,
+                        part: None,
+                    })?;
+                    let status = response.status();
+                    let body = response.text()?;
+                    let descriptions = serde_json::from_str(&body)
+                        .map_err(|_| unexpected_response(status, &body))?;
+                    self.write_cache(event, quest, DESCRIPTION_CACHE_FILE, &body);
+                    descriptions
+                }
+            };
+
+        descriptions.take(part).ok_or(ClientError::NotUnlocked {
+            event,
+            quest,
+            part: Some(part),
         })
     }
 
+    /// Fetches the description of a part as HTML, decrypted with the same key as the input
+    pub fn fetch_description(
+        &self,
+        event: Event,
+        quest: Quest,
+        part: Part,
+    ) -> Result<String, ClientError> {
+        let key = self.fetch_decryption_key(event, quest, part)?;
+        let encrypted = self.fetch_encrypted_description(event, quest, part)?;
+        self.decrypt_input(&encrypted, &key)
+    }
+
+    fn read_cache<T: DeserializeOwned>(&self, event: Event, quest: Quest, name: &str) -> Option<T> {
+        let content = self.cache.as_ref()?.read(event, quest, self.seed, name)?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Caches a response on a best-effort basis, the fetched data is still usable if this fails
+    fn write_cache(&self, event: Event, quest: Quest, name: &str, content: &str) {
+        if let Some(cache) = &self.cache
+            && let Err(e) = cache.write(event, quest, self.seed, name, content)
+        {
+            eprintln!("Could not cache {name} in {}: {e}", cache.dir().display());
+        }
+    }
+
     pub fn decrypt_input(&self, encrypted_hex: &str, key: &str) -> Result<String, ClientError> {
         let encrypted_bytes =
             hex::decode(encrypted_hex).map_err(|e| ClientError::DecryptionError(e.to_string()))?;
@@ -231,7 +625,7 @@ impl Client {
         &self,
         event: Event,
         quest: Quest,
-        part: u8,
+        part: Part,
     ) -> Result<String, ClientError> {
         let encrypted = self.fetch_encrypted_input(event, quest, part)?;
         let key = self.fetch_decryption_key(event, quest, part)?;
@@ -242,11 +636,12 @@ impl Client {
         &self,
         event: Event,
         quest: Quest,
-        part: u8,
+        part: Part,
         answer: impl Into<String>,
-    ) -> Result<String, ClientError> {
+    ) -> Result<SubmissionResponse, ClientError> {
         let url = format!(
-            "{BASE_URL}/api/event/{}/quest/{}/part/{part}/answer",
+            "{}/api/event/{}/quest/{}/part/{part}/answer",
+            self.base_url,
             event.as_u32(),
             quest.as_u8()
         );
@@ -260,13 +655,43 @@ impl Client {
             .post(&url)
             .header("Cookie", format!("everybody-codes={}", self.session))
             .json(&payload)
-            .send()?
-            .error_for_status()?;
-
-        Ok(response.text()?)
+            .send()?;
+        let response = check_status(response, || ClientError::NotUnlocked {
+            event,
+            quest,
+            part: Some(part),
+        })?;
+
+        let status = response.status();
+        let body = response.text()?;
+        serde_json::from_str(&body).map_err(|_| unexpected_response(status, &body))
     }
 
     pub fn seed(&self) -> u32 {
         self.seed
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unexpected_response_excerpt() {
+        let body = format!("<html>\n  <body>{}</body>\n</html>", "é".repeat(200));
+        let err = unexpected_response(StatusCode::OK, &body);
+        let ClientError::UnexpectedResponse { status, excerpt } = &err else {
+            panic!("{err:?}");
+        };
+        assert_eq!(*status, 200);
+        assert!(excerpt.starts_with("<html> <body>é"), "{excerpt}");
+        assert_eq!(excerpt.chars().count(), EXCERPT_LENGTH + 1);
+        assert!(excerpt.ends_with('…'));
+
+        let err = unexpected_response(StatusCode::OK, "not json");
+        assert_eq!(
+            err.to_string(),
+            "unexpected response from the server (HTTP 200): not json"
+        );
+    }
+}
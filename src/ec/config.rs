@@ -0,0 +1,272 @@
+use crate::ec::Part;
+use crate::ec::solution_macro::{
+    InputError, quest_part_id, read_notes, try_read_example_file, try_read_input_file,
+};
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, ValueEnum};
+use itertools::Itertools;
+use std::env;
+use std::fs;
+use std::io::Read;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// How the answers of a quest binary are printed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human readable, with colors
+    #[default]
+    Text,
+    /// One JSON object per part
+    Json,
+}
+
+/// Where the notes of a part are read from instead of `inputs/notes`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+/// An `--input` argument: `path`, `-` for stdin, or either prefixed with a part, e.g. `2=path`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputOverride {
+    /// The part this override applies to, `None` for all parts
+    pub part: Option<Part>,
+    pub source: InputSource,
+}
+
+impl FromStr for InputOverride {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (part, source) = match s.split_once('=') {
+            Some((part, source)) if part.parse::<Part>().is_ok() => (Some(part.parse()?), source),
+            _ => (None, s),
+        };
+        let source = match source {
+            "" => return Err("Input path must not be empty".to_string()),
+            "-" => InputSource::Stdin,
+            path => InputSource::File(PathBuf::from(path)),
+        };
+        Ok(Self { part, source })
+    }
+}
+
+/// Parses a duration like `500ms`, `2s` or `1.5s`
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let (value, unit) = match s.strip_suffix("ms") {
+        Some(value) => (value, 1e-3),
+        None => (s.strip_suffix('s').unwrap_or(s), 1.0),
+    };
+    value
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .and_then(|value| Duration::try_from_secs_f64(value * unit).ok())
+        .ok_or_else(|| format!("Invalid duration {s}, expected e.g. 500ms or 2s"))
+}
+
+/// Parses a non-negative percentage
+fn parse_threshold(s: &str) -> Result<f64, String> {
+    s.trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|threshold| threshold.is_finite() && *threshold >= 0.0)
+        .ok_or_else(|| format!("Invalid threshold {s}, expected a percentage of at least 0"))
+}
+
+/// The time limit per part set with `EC_TIMEOUT`, if any
+fn env_timeout() -> Result<Option<Duration>, String> {
+    match env::var("EC_TIMEOUT") {
+        Ok(timeout) => parse_duration(&timeout)
+            .map(Some)
+            .map_err(|e| format!("EC_TIMEOUT: {e}")),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Command line of a quest binary, parsed once by the `main` of [`solution!`](crate::solution)
+#[derive(Parser, Debug, Clone)]
+#[command(about = "Runs the solution of a quest", long_about = None)]
+pub struct RunConfig {
+    /// Submit the answer of this part (1-3)
+    #[arg(short, long)]
+    pub submit: Option<Part>,
+    /// Only run these parts, e.g. 1,3 (defaults to all)
+    #[arg(short, long, value_delimiter = ',')]
+    pub parts: Vec<Part>,
+    /// Read the notes from this file (`-` for stdin) instead of inputs/notes, for one part with e.g. `2=path`.
+    /// Answers for these notes are never submitted
+    #[arg(short, long)]
+    pub input: Vec<InputOverride>,
+    /// Run on the example notes instead of the real input, never submits
+    #[arg(short, long)]
+    pub example: bool,
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t)]
+    pub format: OutputFormat,
+    /// Run every part repeatedly and report timing statistics instead of a single timing
+    #[arg(short, long, conflicts_with = "submit")]
+    pub bench: bool,
+    /// Time budget per part in bench mode, e.g. 500ms or 2s
+    #[arg(long, default_value = "1s", value_parser = parse_duration)]
+    pub bench_time: Duration,
+    /// Compare the timings with the latest ones of another commit and fail if a part got slower
+    #[arg(short, long)]
+    pub compare: bool,
+    /// Commit to compare with instead of the latest other one
+    #[arg(long, requires = "compare")]
+    pub baseline: Option<String>,
+    /// How much slower than the baseline a part may get, in percent
+    #[arg(long, default_value_t = 20.0, value_parser = parse_threshold)]
+    pub threshold: f64,
+    /// Give up on a part after this long, e.g. 30s (defaults to EC_TIMEOUT, then to the timeout of the quest)
+    #[arg(long, value_parser = parse_duration)]
+    pub timeout: Option<Duration>,
+    /// Stdin is read once, even if it is the input of several parts
+    #[arg(skip)]
+    stdin: OnceLock<Result<String, String>>,
+}
+
+impl Default for RunConfig {
+    /// The configuration of a quest binary run without arguments
+    fn default() -> Self {
+        let mut config = Self::parse_from(["quest"]);
+        config.timeout = env_timeout().unwrap_or_else(|e| {
+            eprintln!("{e}, ignoring it");
+            None
+        });
+        config
+    }
+}
+
+impl RunConfig {
+    /// Parses the arguments of the current process, exiting with a usage message if they are invalid
+    pub fn from_env() -> Self {
+        let mut config = Self::parse();
+        if config.timeout.is_none() {
+            config.timeout = env_timeout()
+                .unwrap_or_else(|e| Self::command().error(ErrorKind::InvalidValue, e).exit());
+        }
+        config
+    }
+
+    /// Whether the given part should be run
+    pub fn runs(&self, part: Part) -> bool {
+        self.parts.is_empty() || self.parts.contains(&part)
+    }
+
+    /// Whether the answer of the given part should be submitted
+    pub fn submits(&self, part: Part) -> bool {
+        self.uses_own_notes(part) && self.submit == Some(part)
+    }
+
+    /// Whether the given part runs on the downloaded notes, not on the example or an input override
+    pub fn uses_own_notes(&self, part: Part) -> bool {
+        !self.example && self.input_override(part).is_none()
+    }
+
+    /// Where the notes of the given part are read from instead of `inputs/notes`, if anywhere else.
+    ///
+    /// An override for the specific part takes precedence over one for all parts.
+    pub fn input_override(&self, part: Part) -> Option<&InputSource> {
+        let overrides = self.input.iter().rev();
+        overrides
+            .clone()
+            .find(|o| o.part == Some(part))
+            .or_else(|| overrides.clone().find(|o| o.part.is_none()))
+            .map(|o| &o.source)
+    }
+
+    /// The recorded answer for the example notes of the given part, if running on them
+    pub fn expected_answer(&self, event: &str, quest: u8, part: Part) -> Option<String> {
+        if !self.example || self.input_override(part).is_some() {
+            return None;
+        }
+        let path = quest_part_id(event, quest, part).expected_path();
+        let expected = fs::read_to_string(path).ok()?;
+        Some(expected.trim_end().to_string())
+    }
+
+    /// Reads the notes for the given part, taking the input override and example mode into account
+    pub fn read_input(&self, event: &str, quest: u8, part: Part) -> Result<String, InputError> {
+        match self.input_override(part) {
+            Some(InputSource::File(path)) => read_notes(path),
+            Some(InputSource::Stdin) => self
+                .stdin
+                .get_or_init(|| {
+                    let mut input = String::new();
+                    std::io::stdin()
+                        .read_to_string(&mut input)
+                        .map_err(|e| e.to_string())?;
+                    Ok(input.lines().join("\n"))
+                })
+                .clone()
+                .map_err(InputError::StdinError),
+            None if self.example => try_read_example_file(event, quest, part),
+            None => try_read_input_file(event, quest, part),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_run_config() {
+        let config = RunConfig::try_parse_from(["quest", "--submit", "2", "-p", "1,2"]).unwrap();
+        assert_eq!(config.submit, Some(Part::Two));
+        assert!(config.runs(Part::One) && config.runs(Part::Two) && !config.runs(Part::Three));
+        assert!(config.submits(Part::Two) && !config.submits(Part::One));
+        assert_eq!(config.format, OutputFormat::Text);
+
+        let config = RunConfig::try_parse_from(["quest", "--format", "json"]).unwrap();
+        assert!(config.runs(Part::Three));
+        assert_eq!(config.format, OutputFormat::Json);
+
+        let config = RunConfig::try_parse_from(["quest", "--example", "--submit", "1"]).unwrap();
+        assert!(!config.submits(Part::One));
+        assert!(RunConfig::try_parse_from(["quest", "--submit", "4"]).is_err());
+
+        let config =
+            RunConfig::try_parse_from(["quest", "--bench", "--bench-time", "250ms"]).unwrap();
+        assert_eq!(config.bench_time, Duration::from_millis(250));
+        assert!(RunConfig::try_parse_from(["quest", "--bench", "--submit", "1"]).is_err());
+        assert!(RunConfig::try_parse_from(["quest", "--bench-time", "soon"]).is_err());
+
+        let config = RunConfig::try_parse_from(["quest", "--threshold", "5.5"]).unwrap();
+        assert_eq!(config.threshold, 5.5);
+        for threshold in ["-1", "NaN", "inf"] {
+            assert!(RunConfig::try_parse_from(["quest", "--threshold", threshold]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_input_overrides() {
+        let config =
+            RunConfig::try_parse_from(["quest", "-i", "all.txt", "-i", "2=-", "--submit", "1"])
+                .unwrap();
+        assert_eq!(
+            config.input_override(Part::One),
+            Some(&InputSource::File(PathBuf::from("all.txt")))
+        );
+        assert_eq!(config.input_override(Part::Two), Some(&InputSource::Stdin));
+        assert!(!config.submits(Part::One));
+
+        let config = RunConfig::try_parse_from(["quest", "-i", "3=a=b.txt"]).unwrap();
+        assert_eq!(config.input_override(Part::Two), None);
+        assert_eq!(
+            config.input_override(Part::Three),
+            Some(&InputSource::File(PathBuf::from("a=b.txt")))
+        );
+        assert_eq!(
+            "x=y".parse::<InputOverride>().unwrap().source,
+            InputSource::File(PathBuf::from("x=y"))
+        );
+    }
+}
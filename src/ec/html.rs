@@ -0,0 +1,491 @@
+use crate::ec::runner::{ANSI_BOLD, ANSI_RESET};
+
+const ANSI_ITALIC: &str = "\x1b[3m";
+const ANSI_UNDERLINE: &str = "\x1b[4m";
+const ANSI_CYAN: &str = "\x1b[36m";
+
+/// Output flavour of [`render`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    /// Plain text with ANSI escape codes for headings, emphasis and code
+    Terminal,
+    Markdown,
+}
+
+/// Converts the HTML of a quest description to readable terminal text
+pub fn html_to_terminal(html: &str) -> String {
+    render(html, Style::Terminal)
+}
+
+/// Converts the HTML of a quest description to Markdown
+pub fn html_to_markdown(html: &str) -> String {
+    render(html, Style::Markdown)
+}
+
+pub fn render(html: &str, style: Style) -> String {
+    let mut renderer = Renderer {
+        style,
+        out: String::new(),
+        pre: 0,
+        skip: 0,
+        lists: Vec::new(),
+    };
+
+    let mut rest = html;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("<!--") {
+            rest = after.find("-->").map_or("", |end| &after[end + 3..]);
+        } else if let Some(after) = rest.strip_prefix('<')
+            && let Some(end) = after.find('>')
+        {
+            renderer.tag(&after[..end]);
+            rest = &after[end + 1..];
+        } else {
+            // Skip the first character, which may be a `<` that does not start a tag
+            let first = rest.chars().next().map_or(1, char::len_utf8);
+            let end = rest[first..].find('<').map_or(rest.len(), |i| i + first);
+            renderer.text(&decode_entities(&rest[..end]));
+            rest = &rest[end..];
+        }
+    }
+
+    renderer.out.trim().to_string() + "\n"
+}
+
+/// Example notes of a part description
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub notes: String,
+    /// Expected answer for the notes, if the description gives one
+    pub answer: Option<String>,
+}
+
+/// Extracts the first example notes block of a part description.
+///
+/// Notes are `<pre>` blocks, preferring those with the `note` class.
+/// The expected answer is only taken from a paragraph after the notes that mentions the "answer" or "result":
+/// the first short `<code>`, `<b>` or `<strong>` text following that word.
+pub fn extract_example(html: &str) -> Option<Example> {
+    let elements = elements(html);
+    let is_pre = |e: &&Element| e.name == "pre";
+    let notes = elements
+        .iter()
+        .filter(is_pre)
+        .find(|e| e.has_class("note"))
+        .or_else(|| elements.iter().find(is_pre))?;
+
+    let answer = elements
+        .iter()
+        .filter(|p| p.name == "p" && p.start > notes.start)
+        .find_map(|p| {
+            let paragraph = p.text.to_ascii_lowercase();
+            let keyword = ["answer", "result"]
+                .iter()
+                .filter_map(|keyword| paragraph.find(keyword))
+                .min()?;
+            elements
+                .iter()
+                .filter(|e| matches!(e.name.as_str(), "code" | "b" | "strong"))
+                .filter(|e| e.start > p.start && e.end <= p.end)
+                // Position of the element in the text of the paragraph
+                .filter(|e| text(&html[p.content_start..e.start]).len() > keyword)
+                .map(|e| e.text.trim())
+                .find(|text| !text.is_empty() && text.len() <= 50 && !text.contains('\n'))
+        })
+        .map(ToString::to_string);
+
+    Some(Example {
+        notes: notes.text.trim_end_matches('\n').to_string(),
+        answer,
+    })
+}
+
+/// An element with its decoded text content
+struct Element {
+    name: String,
+    attributes: String,
+    /// Position of the opening tag
+    start: usize,
+    /// Position right after the opening tag
+    content_start: usize,
+    /// Position of the closing tag
+    end: usize,
+    text: String,
+}
+
+impl Element {
+    fn has_class(&self, class: &str) -> bool {
+        attribute(&self.attributes, "class")
+            .is_some_and(|classes| classes.split_whitespace().any(|c| c == class))
+    }
+}
+
+/// The value of an attribute, quoted or not, or an empty string for an attribute without a value
+fn attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = attributes;
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            return None;
+        }
+        let len = rest
+            .find(|c: char| c == '=' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let key = &rest[..len];
+        rest = rest[len..].trim_start();
+
+        let mut value = "";
+        if let Some(after) = rest.strip_prefix('=') {
+            let after = after.trim_start();
+            (value, rest) = match after.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let inner = &after[1..];
+                    let end = inner.find(quote).unwrap_or(inner.len());
+                    (&inner[..end], inner.get(end + 1..).unwrap_or_default())
+                }
+                _ => after.split_at(after.find(char::is_whitespace).unwrap_or(after.len())),
+            };
+        }
+        if key.eq_ignore_ascii_case(name) {
+            return Some(value);
+        }
+    }
+}
+
+/// Collects every `p`, `pre`, `code`, `b` and `strong` element in document order
+fn elements(html: &str) -> Vec<Element> {
+    let mut elements = Vec::new();
+    let mut open: Vec<Element> = Vec::new();
+
+    let mut pos = 0;
+    while let Some(offset) = html[pos..].find('<') {
+        let start = pos + offset;
+        let Some(len) = html[start..].find('>') else {
+            break;
+        };
+        let tag = &html[start + 1..start + len];
+        let end = start + len + 1;
+
+        let (name, closing) = parse_tag(tag);
+        if matches!(name.as_str(), "p" | "pre" | "code" | "b" | "strong") {
+            if !closing {
+                let attributes = tag[name.len()..].to_string();
+                open.push(Element {
+                    name,
+                    attributes,
+                    start,
+                    content_start: end,
+                    end: start,
+                    text: String::new(),
+                });
+            } else if let Some(i) = open.iter().rposition(|e| e.name == name) {
+                let mut element = open.remove(i);
+                element.end = start;
+                element.text = text(&html[element.content_start..start]);
+                elements.push(element);
+            }
+        }
+        pos = end;
+    }
+
+    elements.sort_by_key(|e| e.start);
+    elements
+}
+
+/// The decoded text of some HTML, without its tags
+fn text(html: &str) -> String {
+    decode_entities(&strip_tags(html))
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Tag name and whether it is a closing tag
+pub(crate) fn parse_tag(tag: &str) -> (String, bool) {
+    let (closing, tag) = match tag.strip_prefix('/') {
+        Some(tag) => (true, tag),
+        None => (false, tag),
+    };
+    let name = tag
+        .split(|c: char| c.is_whitespace() || c == '/')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    (name, closing)
+}
+
+pub(crate) fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').filter(|&end| end <= 10).and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#').and_then(|n| n.parse().ok()))
+                    .and_then(char::from_u32),
+            }?;
+            Some((c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+struct Renderer {
+    style: Style,
+    out: String,
+    /// Depth of nested `<pre>` elements
+    pre: usize,
+    /// Depth of elements whose content is dropped
+    skip: usize,
+    /// Item counters of the open lists, `None` for unordered lists
+    lists: Vec<Option<usize>>,
+}
+
+impl Renderer {
+    fn tag(&mut self, tag: &str) {
+        let (name, closing) = parse_tag(tag);
+        if matches!(name.as_str(), "script" | "style") {
+            if closing {
+                self.skip = self.skip.saturating_sub(1);
+            } else {
+                self.skip += 1;
+            }
+            return;
+        }
+        if self.skip > 0 {
+            return;
+        }
+
+        let markdown = self.style == Style::Markdown;
+        match (name.as_str(), closing) {
+            ("h1" | "h2" | "h3" | "h4" | "h5" | "h6", false) => {
+                self.block(2);
+                let level = name[1..].parse().unwrap_or(1);
+                if markdown {
+                    self.out.push_str(&"#".repeat(level));
+                    self.out.push(' ');
+                } else {
+                    self.out.push_str(ANSI_BOLD);
+                    self.out.push_str(ANSI_UNDERLINE);
+                }
+            }
+            ("h1" | "h2" | "h3" | "h4" | "h5" | "h6", true) => {
+                if !markdown {
+                    self.out.push_str(ANSI_RESET);
+                }
+                self.block(2);
+            }
+            ("p" | "div" | "blockquote" | "table", _) => self.block(2),
+            ("tr", _) => self.block(1),
+            ("br", _) => self.out.push('\n'),
+            ("hr", _) => {
+                self.block(2);
+                self.out.push_str("---");
+                self.block(2);
+            }
+            ("pre", false) => {
+                self.block(2);
+                if markdown {
+                    self.out.push_str("```\n");
+                }
+                self.pre += 1;
+            }
+            ("pre", true) => {
+                self.pre = self.pre.saturating_sub(1);
+                if !self.out.ends_with('\n') {
+                    self.out.push('\n');
+                }
+                if markdown {
+                    self.out.push_str("```");
+                }
+                self.block(2);
+            }
+            ("ul", false) => {
+                self.block(1);
+                self.lists.push(None);
+            }
+            ("ol", false) => {
+                self.block(1);
+                self.lists.push(Some(0));
+            }
+            ("ul" | "ol", true) => {
+                self.lists.pop();
+                self.block(2);
+            }
+            ("li", false) => {
+                self.block(1);
+                let indent = "  ".repeat(self.lists.len().saturating_sub(1));
+                self.out.push_str(&indent);
+                match self.lists.last_mut() {
+                    Some(Some(n)) => {
+                        *n += 1;
+                        let n = *n;
+                        self.out.push_str(&format!("{n}. "));
+                    }
+                    _ => self.out.push_str("- "),
+                }
+            }
+            _ if self.pre > 0 => {}
+            ("code", false) => self.out.push_str(if markdown { "`" } else { ANSI_CYAN }),
+            ("em" | "i", false) => self.out.push_str(if markdown { "*" } else { ANSI_ITALIC }),
+            ("strong" | "b", false) => self.out.push_str(if markdown { "**" } else { ANSI_BOLD }),
+            ("code", true) => self.out.push_str(if markdown { "`" } else { ANSI_RESET }),
+            ("em" | "i", true) => self.out.push_str(if markdown { "*" } else { ANSI_RESET }),
+            ("strong" | "b", true) => self.out.push_str(if markdown { "**" } else { ANSI_RESET }),
+            _ => {}
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if self.skip > 0 {
+            return;
+        }
+        if self.pre > 0 {
+            if self.style == Style::Terminal {
+                // Indent code blocks so they stand out from the surrounding text
+                for c in text.chars() {
+                    if c != '\n' && self.out.ends_with('\n') {
+                        self.out.push_str("    ");
+                    }
+                    self.out.push(c);
+                }
+            } else {
+                self.out.push_str(text);
+            }
+            return;
+        }
+
+        let at_line_start = self.out.is_empty() || self.out.ends_with('\n');
+        let mut words = text.split_whitespace().peekable();
+        if words.peek().is_none() {
+            if !at_line_start && !text.is_empty() && !self.out.ends_with(' ') {
+                self.out.push(' ');
+            }
+            return;
+        }
+
+        if text.starts_with(char::is_whitespace) && !at_line_start && !self.out.ends_with(' ') {
+            self.out.push(' ');
+        }
+        let collapsed = words.collect::<Vec<_>>().join(" ");
+        self.out.push_str(&collapsed);
+        if text.ends_with(char::is_whitespace) {
+            self.out.push(' ');
+        }
+    }
+
+    /// Ends the current line and makes sure that there are `newlines` line breaks before the next text
+    fn block(&mut self, newlines: usize) {
+        while self.out.ends_with(' ') {
+            self.out.pop();
+        }
+        if self.out.is_empty() {
+            return;
+        }
+        let existing = self.out.len() - self.out.trim_end_matches('\n').len();
+        for _ in existing..newlines {
+            self.out.push('\n');
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const HTML: &str = "<h2>Part I</h2><p>The list of <em>names</em> is:</p>\
+        <pre class=\"note\">Vyrdax,Drakzyph\n\nR3,L2</pre>\
+        <p>Then <b>repeat</b>. The answer is <code>Fyrryn</code> &amp; <strong>done</strong>.</p>";
+
+    #[test]
+    fn test_html_to_markdown() {
+        assert_eq!(
+            html_to_markdown(HTML),
+            "## Part I\n\nThe list of *names* is:\n\n```\nVyrdax,Drakzyph\n\nR3,L2\n```\n\nThen **repeat**. The answer is `Fyrryn` & **done**.\n"
+        );
+    }
+
+    #[test]
+    fn test_extract_example() {
+        assert_eq!(
+            extract_example(HTML),
+            Some(Example {
+                notes: "Vyrdax,Drakzyph\n\nR3,L2".to_string(),
+                answer: Some("Fyrryn".to_string()),
+            })
+        );
+        assert_eq!(
+            extract_example("<pre>1</pre><p>Count the <b>gears</b>.</p>")
+                .unwrap()
+                .answer,
+            None
+        );
+        assert_eq!(extract_example("<p>No example</p>"), None);
+    }
+
+    #[test]
+    fn test_extract_example_matches_words() {
+        let html = "<pre class=\"notebook\">wrong</pre><pre data-x='1' class='example note'>1,2</pre>\
+            <p><a href=\"/results\">Previously</a>, <b>3</b> was the answer; the answer is now <code>5</code>.</p>";
+        assert_eq!(
+            extract_example(html),
+            Some(Example {
+                notes: "1,2".to_string(),
+                answer: Some("5".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_multibyte_text_after_tags() {
+        assert_eq!(
+            html_to_markdown("<p>é is a letter</p><p>—<em>ñ</em></p>"),
+            "é is a letter\n\n—*ñ*\n"
+        );
+    }
+
+    #[test]
+    fn test_html_to_terminal() {
+        assert_eq!(
+            html_to_terminal("<p>a <code>b</code> <b>c</b></p>"),
+            format!("a {ANSI_CYAN}b{ANSI_RESET} {ANSI_BOLD}c{ANSI_RESET}\n")
+        );
+    }
+}
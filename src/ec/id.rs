@@ -0,0 +1,129 @@
+use crate::ec::paths::{inputs_dir, project_root};
+use crate::ec::{Event, Part, Quest};
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Identifies a quest of an event or story, written as e.g. `e2025-01`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct QuestId {
+    pub event: Event,
+    pub quest: Quest,
+}
+
+impl QuestId {
+    pub fn new(event: Event, quest: Quest) -> Self {
+        Self { event, quest }
+    }
+
+    pub fn part(self, part: Part) -> QuestPartId {
+        QuestPartId { quest: self, part }
+    }
+
+    /// Name of the solution binary, e.g. `quest_e2025_01`
+    pub fn bin_name(&self) -> String {
+        format!("quest_{}_{}", self.event, self.quest)
+    }
+
+    pub fn bin_path(&self) -> PathBuf {
+        project_root()
+            .join("src/bin")
+            .join(format!("{}.rs", self.bin_name()))
+    }
+
+    pub fn description_path(&self) -> PathBuf {
+        inputs_dir().join("descriptions").join(format!("{self}.md"))
+    }
+}
+
+impl FromStr for QuestId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (event, quest) = s
+            .split_once('-')
+            .ok_or_else(|| format!("Invalid quest id {s}, expected e.g. e2025-01"))?;
+        Ok(Self::new(event.parse()?, quest.parse()?))
+    }
+}
+
+impl fmt::Display for QuestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.event, self.quest)
+    }
+}
+
+/// Identifies a part of a quest, written as e.g. `e2025-01-2`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct QuestPartId {
+    pub quest: QuestId,
+    pub part: Part,
+}
+
+impl QuestPartId {
+    pub fn new(event: Event, quest: Quest, part: Part) -> Self {
+        QuestId::new(event, quest).part(part)
+    }
+
+    pub fn event(&self) -> Event {
+        self.quest.event
+    }
+
+    pub fn notes_path(&self) -> PathBuf {
+        inputs_dir().join("notes").join(format!("{self}.txt"))
+    }
+
+    pub fn example_path(&self) -> PathBuf {
+        inputs_dir().join("examples").join(format!("{self}.txt"))
+    }
+
+    /// Expected answer for the example notes
+    pub fn expected_path(&self) -> PathBuf {
+        inputs_dir()
+            .join("examples")
+            .join(format!("{self}.expected.txt"))
+    }
+
+    pub fn description_path(&self) -> PathBuf {
+        inputs_dir().join("descriptions").join(format!("{self}.md"))
+    }
+}
+
+impl FromStr for QuestPartId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (quest, part) = s
+            .rsplit_once('-')
+            .ok_or_else(|| format!("Invalid quest part id {s}, expected e.g. e2025-01-2"))?;
+        Ok(quest.parse::<QuestId>()?.part(part.parse()?))
+    }
+}
+
+impl fmt::Display for QuestPartId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.quest, self.part)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_and_format() {
+        let id: QuestPartId = "e2025-3-2".parse().unwrap();
+        assert_eq!(id.to_string(), "e2025-03-2");
+        assert_eq!(id.quest.to_string(), "e2025-03");
+        assert_eq!(id.quest.bin_name(), "quest_e2025_03");
+        assert!(id.notes_path().ends_with("inputs/notes/e2025-03-2.txt"));
+
+        assert!("e2025-03".parse::<QuestPartId>().is_err());
+        assert!("e2025-03-4".parse::<QuestPartId>().is_err());
+        assert_eq!(
+            "s1-01".parse::<QuestId>().unwrap().bin_name(),
+            "quest_s1_01"
+        );
+    }
+}
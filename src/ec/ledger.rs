@@ -0,0 +1,199 @@
+use crate::Quest;
+use crate::ec::client::SubmissionResponse;
+use crate::ec::record_file::{RecordFile, RecordFileError, timestamp};
+use crate::ec::{Event, Part};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use thiserror::Error;
+
+const LEDGER_FILE: &str = "submissions.json";
+
+#[derive(Error, Debug)]
+pub enum LedgerError {
+    #[error("{event}-{quest}-{part} was already solved with answer {answer:?}")]
+    AlreadySolved {
+        event: Event,
+        quest: Quest,
+        part: Part,
+        answer: String,
+    },
+    #[error("answer {answer:?} for {event}-{quest}-{part} was already submitted and is wrong")]
+    KnownWrong {
+        event: Event,
+        quest: Quest,
+        part: Part,
+        answer: String,
+    },
+    #[error(transparent)]
+    FileError(#[from] RecordFileError),
+}
+
+/// A single submission as recorded in the ledger
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Submission {
+    pub event: String,
+    pub quest: u8,
+    pub part: Part,
+    pub seed: u32,
+    pub answer: String,
+    pub response: SubmissionResponse,
+    /// Seconds since the UNIX epoch
+    pub timestamp: u64,
+}
+
+impl Submission {
+    fn matches(&self, event: Event, quest: Quest, part: Part, seed: u32) -> bool {
+        self.event == event.to_string()
+            && self.quest == quest.as_u8()
+            && self.part == part
+            && self.seed == seed
+    }
+}
+
+/// Persistent record of every answer that was sent to the API
+#[derive(Debug)]
+pub struct Ledger {
+    file: RecordFile<Submission>,
+}
+
+impl Ledger {
+    /// Loads the ledger from `submissions.json` in the inputs directory, starting empty if it does not exist yet
+    pub fn load_default() -> Result<Self, LedgerError> {
+        Ok(Self {
+            file: RecordFile::load_default(LEDGER_FILE)?,
+        })
+    }
+
+    /// Loads the ledger from the given file, starting empty if it does not exist yet
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LedgerError> {
+        Ok(Self {
+            file: RecordFile::load(path)?,
+        })
+    }
+
+    pub fn submissions(&self) -> &[Submission] {
+        self.file.records()
+    }
+
+    /// Checks whether the answer may be submitted.
+    ///
+    /// Refuses answers for parts that were already solved and answers that are already known to be wrong.
+    pub fn check(
+        &self,
+        event: Event,
+        quest: Quest,
+        part: Part,
+        seed: u32,
+        answer: &str,
+    ) -> Result<(), LedgerError> {
+        let mut previous = self
+            .submissions()
+            .iter()
+            .filter(|s| s.matches(event, quest, part, seed));
+
+        if let Some(solved) = previous.clone().find(|s| s.response.correct) {
+            return Err(LedgerError::AlreadySolved {
+                event,
+                quest,
+                part,
+                answer: solved.answer.clone(),
+            });
+        }
+
+        if previous.any(|s| !s.response.correct && s.answer == answer) {
+            return Err(LedgerError::KnownWrong {
+                event,
+                quest,
+                part,
+                answer: answer.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// The answer that was accepted as correct, if the part was solved
+    pub fn correct_answer(
+        &self,
+        event: Event,
+        quest: Quest,
+        part: Part,
+        seed: u32,
+    ) -> Option<&str> {
+        self.submissions()
+            .iter()
+            .find(|s| s.matches(event, quest, part, seed) && s.response.correct)
+            .map(|s| s.answer.as_str())
+    }
+
+    /// Appends a submission and writes the ledger back to disk
+    pub fn record(
+        &mut self,
+        event: Event,
+        quest: Quest,
+        part: Part,
+        seed: u32,
+        answer: &str,
+        response: &SubmissionResponse,
+    ) -> Result<(), LedgerError> {
+        let submission = Submission {
+            event: event.to_string(),
+            quest: quest.as_u8(),
+            part,
+            seed,
+            answer: answer.to_string(),
+            response: response.clone(),
+            timestamp: timestamp(),
+        };
+        Ok(self
+            .file
+            .update(|submissions| submissions.push(submission))?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn response(correct: bool) -> SubmissionResponse {
+        serde_json::from_value(serde_json::json!({ "correct": correct })).unwrap()
+    }
+
+    #[test]
+    fn test_refuses_known_answers() {
+        let path = std::env::temp_dir().join(format!("ec-ledger-{}.json", std::process::id()));
+        let event: Event = "e2025".parse().unwrap();
+        let quest: Quest = 1.try_into().unwrap();
+
+        let mut ledger = Ledger::load(&path).unwrap();
+        assert!(ledger.check(event, quest, Part::One, 13, "a").is_ok());
+
+        ledger
+            .record(event, quest, Part::One, 13, "a", &response(false))
+            .unwrap();
+        assert!(matches!(
+            ledger.check(event, quest, Part::One, 13, "a"),
+            Err(LedgerError::KnownWrong { .. })
+        ));
+        assert!(ledger.check(event, quest, Part::One, 13, "b").is_ok());
+        assert!(ledger.check(event, quest, Part::One, 14, "a").is_ok());
+
+        ledger
+            .record(event, quest, Part::One, 13, "b", &response(true))
+            .unwrap();
+        let ledger = Ledger::load(&path).unwrap();
+        assert!(matches!(
+            ledger.check(event, quest, Part::One, 13, "c"),
+            Err(LedgerError::AlreadySolved { .. })
+        ));
+        assert!(ledger.check(event, quest, Part::Two, 13, "c").is_ok());
+        assert_eq!(
+            ledger.correct_answer(event, quest, Part::One, 13),
+            Some("b")
+        );
+        assert_eq!(ledger.correct_answer(event, quest, Part::Two, 13), None);
+
+        fs::remove_file(path).unwrap();
+    }
+}
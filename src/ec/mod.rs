@@ -1,11 +1,32 @@
+pub mod alloc;
+pub mod bench;
+pub mod cache;
+pub mod catalog;
 pub mod client;
+pub mod config;
 pub mod event;
+pub mod html;
+pub mod id;
+pub mod ledger;
+pub mod panics;
+pub mod part;
+pub mod paths;
 pub mod quest;
+pub mod record_file;
 pub mod runner;
+pub mod schedule;
+pub mod solution;
 pub mod solution_macro;
+pub mod submit;
+pub mod timings;
 
-pub use client::Client;
+pub use client::{Client, ClientBuilder};
+pub use config::RunConfig;
 pub use event::Event;
+pub use id::{QuestId, QuestPartId};
+pub use part::Part;
 pub use quest::Quest;
 pub use runner::run_part;
-pub use solution_macro::{read_example_file, read_input_file};
+pub use solution_macro::{
+    read_example_file, read_input_file, try_read_example_file, try_read_input_file,
+};
@@ -0,0 +1,95 @@
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+thread_local! {
+    /// Number of [`catch_panic`] calls running on this thread
+    static CATCHING: Cell<usize> = const { Cell::new(0) };
+    /// Location of the last panic caught on this thread
+    static LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+static HOOK: Once = Once::new();
+
+/// A panic caught while running a solution
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaughtPanic {
+    pub message: String,
+    /// Source location of the panic, e.g. `src/bin/quest_e2025_01.rs:38:8`
+    pub location: Option<String>,
+}
+
+impl fmt::Display for CaughtPanic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "{} at {location}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// Runs `f`, catching an unwinding panic instead of letting it abort the caller.
+///
+/// The default panic output is suppressed while `f` runs; the panic is returned for the caller to report.
+/// Panics with `panic = "abort"` cannot be caught.
+pub fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, CaughtPanic> {
+    install_hook();
+
+    CATCHING.with(|c| c.set(c.get() + 1));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.with(|c| c.set(c.get() - 1));
+
+    result.map_err(|payload| CaughtPanic {
+        message: panic_message(payload.as_ref()),
+        location: LOCATION.with(|l| l.borrow_mut().take()),
+    })
+}
+
+/// Records panic locations for [`catch_panic`], deferring to the previous hook for other panics
+fn install_hook() {
+    HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING.with(Cell::get) > 0 {
+                let location = info.location().map(ToString::to_string);
+                LOCATION.with(|l| *l.borrow_mut() = location);
+            } else {
+                previous(info);
+            }
+        }));
+    });
+}
+
+/// The message of a panic payload, which is a `&str` or a `String` for `panic!` with a message
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catch_panic() {
+        assert_eq!(catch_panic(|| 42), Ok(42));
+
+        let line = line!() + 1;
+        let caught = catch_panic(|| panic!("index {} out of bounds", 3)).unwrap_err();
+        assert_eq!(caught.message, "index 3 out of bounds");
+        assert!(
+            caught
+                .to_string()
+                .contains(&format!("at src/ec/panics.rs:{line}:")),
+            "{caught}"
+        );
+
+        let caught = catch_panic(|| catch_panic(|| panic!("inner")));
+        assert_eq!(caught.unwrap().unwrap_err().message, "inner");
+    }
+}
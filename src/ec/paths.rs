@@ -0,0 +1,33 @@
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// Marks the project root for projects that are not found through their `Cargo.toml`
+pub const ROOT_MARKER: &str = ".ec-root";
+
+/// Finds the project root by walking up from the current directory.
+///
+/// The root is the first directory containing a [`ROOT_MARKER`] or a `Cargo.toml`,
+/// falling back to the current directory if there is none.
+pub fn project_root() -> PathBuf {
+    let cwd = env::current_dir().unwrap_or_default();
+    cwd.ancestors()
+        .find(|dir| dir.join(ROOT_MARKER).exists() || dir.join("Cargo.toml").exists())
+        .unwrap_or(&cwd)
+        .to_path_buf()
+}
+
+/// The directory containing notes, examples, descriptions and the submission ledger.
+///
+/// Uses `EC_INPUTS_DIR` if set, relative to the project root, and `inputs` in the project root otherwise.
+pub fn inputs_dir() -> PathBuf {
+    let root = project_root();
+    match env::var_os("EC_INPUTS_DIR") {
+        Some(dir) => root.join(dir),
+        None => root.join("inputs"),
+    }
+}
+
+/// Shortens a path inside the project root for display
+pub fn relative(path: &Path) -> &Path {
+    path.strip_prefix(project_root()).unwrap_or(path)
+}
@@ -6,6 +6,8 @@ use std::str::FromStr;
 pub struct Quest(u8);
 
 impl Quest {
+    pub const FIRST: Quest = Quest(1);
+
     pub fn as_u8(&self) -> u8 {
         self.0
     }
@@ -0,0 +1,63 @@
+use crate::ec::paths::inputs_dir;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum RecordFileError {
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("JSON error: {0}")]
+    JsonError(#[from] serde_json::Error),
+}
+
+/// A list of records kept in a JSON file, like the submission ledger and the timings
+#[derive(Debug)]
+pub struct RecordFile<T> {
+    path: PathBuf,
+    records: Vec<T>,
+}
+
+impl<T: Serialize + DeserializeOwned> RecordFile<T> {
+    /// Loads the records from the file with the given name in the inputs directory
+    pub fn load_default(name: &str) -> Result<Self, RecordFileError> {
+        Self::load(inputs_dir().join(name))
+    }
+
+    /// Loads the records from the given file, starting empty if it does not exist yet
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RecordFileError> {
+        let path = path.as_ref().to_path_buf();
+        let records = if path.exists() {
+            serde_json::from_str(&fs::read_to_string(&path)?)?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self { path, records })
+    }
+
+    pub fn records(&self) -> &[T] {
+        &self.records
+    }
+
+    /// Changes the records and writes the file back to disk
+    pub fn update(&mut self, f: impl FnOnce(&mut Vec<T>)) -> Result<(), RecordFileError> {
+        f(&mut self.records);
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(&self.records)?)?;
+        Ok(())
+    }
+}
+
+/// Seconds since the UNIX epoch, for timestamping new records
+pub fn timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
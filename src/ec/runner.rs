@@ -1,48 +1,219 @@
-use crate::ec::Event;
-use crate::{Client, Quest};
-use std::env;
+use crate::Quest;
+use crate::ec::alloc::{self, AllocMeasurement, AllocStats, format_bytes};
+use crate::ec::client::SubmissionResponse;
+use crate::ec::config::{OutputFormat, RunConfig};
+use crate::ec::panics::{CaughtPanic, catch_panic};
+use crate::ec::solution::{PartOutcome, PartStatus};
+use crate::ec::solution_macro::InputError;
+use crate::ec::submit::{format_submit_error, submit_answer};
+use crate::ec::{Event, Part};
+use serde_json::json;
 use std::fmt::{Debug, Display};
-use std::time::Instant;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
 
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 pub const ANSI_GREEN: &str = "\x1b[32m";
 pub const ANSI_RED: &str = "\x1b[31m";
 
+/// Stack size of the worker threads that run the parts, as deep recursion is common in solutions
+const WORKER_STACK_SIZE: usize = 64 * 1024 * 1024;
+
+/// Set once a part timed out, as its worker thread keeps running
+static TIMED_OUT: AtomicBool = AtomicBool::new(false);
+
+/// Whether a part of this process timed out and still competes with the later parts for the CPU
+pub fn part_timed_out() -> bool {
+    TIMED_OUT.load(Ordering::Relaxed)
+}
+
+/// The answer of a part, or why there is none, in a form that can be sent between threads
+pub struct Evaluation {
+    result: Result<Result<String, String>, CaughtPanic>,
+    duration: Duration,
+    memory: Option<AllocStats>,
+}
+
+/// Runs a part on its notes and formats the answer, catching a panic and measuring heap usage if enabled
+pub fn evaluate<'a, A: Answer + Sized>(
+    func: impl FnOnce(&'a str) -> A,
+    input: &'a str,
+) -> Evaluation
+where
+    <A as Answer>::Output: Display,
+    <A as Answer>::Error: Debug,
+{
+    let measurement = alloc::measure();
+    let timer = Instant::now();
+    let result = catch_panic(|| {
+        func(input)
+            .unpack()
+            .map(|answer| answer.to_string())
+            .map_err(|e| format!("{e:?}"))
+    });
+    let duration = timer.elapsed();
+    Evaluation {
+        result,
+        duration,
+        memory: measurement.map(AllocMeasurement::finish),
+    }
+}
+
 pub fn run_part<'a, A: Answer + Sized>(
     func: impl FnOnce(&'a str) -> A + 'a,
     input: &'a str,
     event: &str,
     quest: u8,
-    part: u8,
-) where
+    part: Part,
+    config: &RunConfig,
+) -> PartOutcome
+where
     <A as Answer>::Output: Display,
     <A as Answer>::Error: Debug,
 {
-    let event: Event = event.parse().expect("invalid event/story");
-    let quest: Quest = quest.try_into().expect("invalid quest");
-
-    // Print result inline
     if input.is_empty() {
-        println!("Part {}: -", part);
-        return;
+        return report_skipped(part, config);
     }
+    report_evaluation(evaluate(func, input), event, quest, part, config)
+}
 
+/// Like [`run_part`], but runs the part on a worker thread and gives up on it after `timeout`, if any.
+///
+/// The worker thread cannot be stopped: after a timeout it keeps running in the background until
+/// the process exits, see [`part_timed_out`].
+pub fn run_part_with_timeout(
+    func: impl FnOnce(&str) -> Evaluation + Send + 'static,
+    input: String,
+    event: &str,
+    quest: u8,
+    part: Part,
+    timeout: Option<Duration>,
+    config: &RunConfig,
+) -> PartOutcome {
+    if input.is_empty() {
+        return report_skipped(part, config);
+    }
     let timer = Instant::now();
-    let result = func(input);
-    let duration = timer.elapsed();
+    let (sender, receiver) = mpsc::channel();
+    thread::Builder::new()
+        .name(format!("part {part}"))
+        .stack_size(WORKER_STACK_SIZE)
+        .spawn(move || sender.send(func(&input)))
+        .expect("failed to spawn a worker thread");
+
+    let received = match timeout {
+        Some(timeout) => match receiver.recv_timeout(timeout) {
+            Err(RecvTimeoutError::Timeout) => {
+                TIMED_OUT.store(true, Ordering::Relaxed);
+                return report_timeout(part, timeout, config);
+            }
+            received => received.ok(),
+        },
+        None => receiver.recv().ok(),
+    };
+    match received {
+        Some(evaluation) => report_evaluation(evaluation, event, quest, part, config),
+        None => {
+            let caught = CaughtPanic {
+                message: "worker thread exited without an answer".to_string(),
+                location: None,
+            };
+            report_panic(part, &caught, timer.elapsed(), None, config)
+        }
+    }
+}
+
+fn report_skipped(part: Part, config: &RunConfig) -> PartOutcome {
+    match config.format {
+        OutputFormat::Text => println!("Part {}: -", part),
+        OutputFormat::Json => println!("{}", json!({ "part": part, "answer": null })),
+    }
+    PartOutcome::new(part, PartStatus::Skipped)
+}
+
+/// Prints the answer of a part, compares it with the expected one and submits it if requested
+fn report_evaluation(
+    evaluation: Evaluation,
+    event: &str,
+    quest: u8,
+    part: Part,
+    config: &RunConfig,
+) -> PartOutcome {
+    let expected = config.expected_answer(event, quest, part);
+    let event: Event = event.parse().expect("invalid event/story");
+    let quest: Quest = quest.try_into().expect("invalid quest");
+    let duration = evaluation.duration;
+    let memory = evaluation.memory;
+    let timing = format_timing(duration, memory);
+
+    let result = match evaluation.result {
+        Ok(result) => result,
+        Err(caught) => return report_panic(part, &caught, duration, expected, config),
+    };
+
+    let outcome = PartOutcome {
+        part,
+        answer: result.as_ref().ok().cloned(),
+        expected: expected.clone(),
+        duration: Some(duration),
+        memory,
+        status: match &result {
+            Ok(_) => PartStatus::Solved,
+            Err(e) => PartStatus::Failed(e.clone()),
+        },
+    };
+    if config.format == OutputFormat::Json {
+        let (answer, error) = match &result {
+            Ok(answer) => (Some(answer.clone()), None),
+            Err(e) => (None, Some(e.clone())),
+        };
+        println!(
+            "{}",
+            json!({
+                "part": part,
+                "answer": answer,
+                "error": error,
+                "expected": expected,
+                "seconds": duration.as_secs_f64(),
+                "memory": memory.map(|m| json!({
+                    "peak": m.peak,
+                    "allocated": m.allocated,
+                    "allocations": m.allocations,
+                })),
+            })
+        );
+
+        // Keep stdout machine readable
+        if let Ok(answer) = &result
+            && let Some(submission_info) = check_and_submit(answer, event, quest, part, config)
+        {
+            eprintln!("Part {part}: {submission_info}");
+        }
+        return outcome;
+    }
 
-    match result.unpack() {
-        Ok(answer) => {
-            let answer_str = answer.to_string();
+    // Print result inline
+    match result {
+        Ok(answer_str) => {
             if answer_str.contains('\n') {
-                println!("Part {part}: (multiline) ({duration:?})");
+                println!("Part {part}: (multiline) ({timing})");
                 println!("{}", answer_str);
+                if let Some(expected) = &expected {
+                    println!("{}", format_expectation(&answer_str, expected));
+                }
             } else {
-                print!("Part {part}: {ANSI_BOLD}{answer_str}{ANSI_RESET} ({duration:?})",);
+                print!("Part {part}: {ANSI_BOLD}{answer_str}{ANSI_RESET} ({timing})",);
+                if let Some(expected) = &expected {
+                    print!(" - {}", format_expectation(&answer_str, expected));
+                }
 
                 // Check if we should submit and get response inline
-                if let Some(submission_info) = check_and_submit(&answer_str, event, quest, part) {
+                if let Some(submission_info) =
+                    check_and_submit(&answer_str, event, quest, part, config)
+                {
                     print!(" - {submission_info}");
                 }
 
@@ -50,9 +221,105 @@ pub fn run_part<'a, A: Answer + Sized>(
             }
         }
         Err(e) => {
-            println!("Part {part}: - ({e:?})");
+            println!("Part {part}: - ({e})");
+        }
+    }
+
+    outcome
+}
+
+/// The duration of a part, followed by its heap usage if measured
+fn format_timing(duration: Duration, memory: Option<AllocStats>) -> String {
+    match memory {
+        Some(m) => format!(
+            "{duration:?}, peak {}, {} in {} allocations",
+            format_bytes(m.peak),
+            format_bytes(m.allocated),
+            m.allocations
+        ),
+        None => format!("{duration:?}"),
+    }
+}
+
+/// Compares an answer for the example notes with the expected one
+fn format_expectation(answer: &str, expected: &str) -> String {
+    if answer == expected {
+        format!("{ANSI_GREEN}✓ as expected{ANSI_RESET}")
+    } else if expected.contains('\n') {
+        format!("{ANSI_RED}✗ expected:{ANSI_RESET}\n{expected}")
+    } else {
+        format!("{ANSI_RED}✗ expected {ANSI_BOLD}{expected}{ANSI_RESET}")
+    }
+}
+
+/// Reports a part that panicked, so that the other parts can still run
+pub(crate) fn report_panic(
+    part: Part,
+    caught: &CaughtPanic,
+    duration: Duration,
+    expected: Option<String>,
+    config: &RunConfig,
+) -> PartOutcome {
+    match config.format {
+        OutputFormat::Text => {
+            println!("Part {part}: {ANSI_RED}panicked: {caught}{ANSI_RESET} ({duration:?})")
         }
+        OutputFormat::Json => println!(
+            "{}",
+            json!({
+                "part": part,
+                "answer": null,
+                "error": format!("panicked: {caught}"),
+                "expected": expected,
+                "seconds": duration.as_secs_f64(),
+            })
+        ),
+    }
+    PartOutcome {
+        part,
+        answer: None,
+        expected,
+        duration: Some(duration),
+        memory: None,
+        status: PartStatus::Panicked(caught.to_string()),
+    }
+}
+
+/// Reports a part that took longer than its time limit
+fn report_timeout(part: Part, timeout: Duration, config: &RunConfig) -> PartOutcome {
+    match config.format {
+        OutputFormat::Text => {
+            println!("Part {part}: {ANSI_RED}timed out after {timeout:?}{ANSI_RESET}")
+        }
+        OutputFormat::Json => println!(
+            "{}",
+            json!({
+                "part": part,
+                "answer": null,
+                "error": format!("timed out after {timeout:?}"),
+                "seconds": timeout.as_secs_f64(),
+            })
+        ),
+    }
+    PartOutcome::new(part, PartStatus::TimedOut(timeout))
+}
+
+/// Reports a part whose notes could not be read, so that the other parts can still run
+pub fn report_input_error(part: Part, e: &InputError, config: &RunConfig) -> PartOutcome {
+    let reason = if e.is_missing() {
+        "input missing"
+    } else {
+        "input unreadable"
+    };
+    match config.format {
+        OutputFormat::Text => println!("Part {part}: - ({reason})"),
+        OutputFormat::Json => println!(
+            "{}",
+            json!({ "part": part, "answer": null, "error": reason })
+        ),
     }
+    eprintln!("{ANSI_RED}{e}{ANSI_RESET}");
+    PartOutcome::new(part, PartStatus::NoInput)
 }
 
 pub trait Answer {
@@ -80,67 +347,140 @@ impl<T, E> Answer for Result<T, E> {
     }
 }
 
-fn check_and_submit(result: &str, event: Event, quest: Quest, part: u8) -> Option<String> {
-    let args: Vec<String> = env::args().collect();
+fn check_and_submit(
+    result: &str,
+    event: Event,
+    quest: Quest,
+    part: Part,
+    config: &RunConfig,
+) -> Option<String> {
+    if !config.submits(part) {
+        return (config.submit == Some(part))
+            .then(|| "not submitted, not running on your notes".to_string());
+    }
 
-    // Check if we should submit AND if this is the part to submit
-    let should_submit = args
-        .iter()
-        .position(|x| x == "--submit")
-        .and_then(|idx| args.get(idx + 1))
-        .and_then(|s| s.parse::<u8>().ok())
-        .map(|submit_part| submit_part == part)
-        .unwrap_or(false);
+    Some(submit_answer(event, quest, part, result).unwrap_or_else(|e| format_submit_error(&e)))
+}
 
-    if !should_submit {
-        return None;
-    }
+pub fn format_submission_response(response: &SubmissionResponse) -> String {
+    if response.correct {
+        let mut parts = vec![format!("{ANSI_GREEN}✓ Correct answer!{ANSI_RESET}")];
 
-    match Client::try_new() {
-        Ok(client) => match client.submit_answer(event, quest, part, result) {
-            Ok(response) => format_submission_response(&response),
-            Err(e) => Some(format!(
-                "{}✗ Submission failed: {}{}",
-                ANSI_RED, e, ANSI_RESET
-            )),
-        },
-        Err(e) => Some(format!("{ANSI_RED}✗ Client error: {e}{ANSI_RESET}")),
+        if let Some(local_place) = response.local_place
+            && local_place > 0
+        {
+            parts.push(format!("Local rank: #{local_place}"));
+        }
+        if let Some(global_place) = response.global_place
+            && global_place > 0
+        {
+            parts.push(format!("Global rank: #{global_place}"));
+        }
+        if let Some(global_score) = response.global_score
+            && global_score > 0
+        {
+            parts.push(format!("Score: {global_score}"));
+        }
+
+        parts.join(" - ")
+    } else {
+        let mut msg = format!("{}✗ Incorrect answer{}", ANSI_RED, ANSI_RESET);
+
+        if !response.length_correct {
+            msg.push_str(" (wrong length)");
+        } else if !response.first_correct {
+            msg.push_str(" (wrong first character)");
+        }
+
+        msg
     }
 }
 
-fn format_submission_response(response: &str) -> Option<String> {
-    // Try to parse as JSON
-    if let Ok(json) = serde_json::from_str::<serde_json::Value>(response) {
-        let correct = json
-            .get("correct")
-            .and_then(|v| v.as_bool())
-            .unwrap_or(false);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        if correct {
-            let mut parts = vec![format!("{ANSI_GREEN}✓ Correct answer!{ANSI_RESET}")];
+    #[test]
+    fn test_run_part_catches_panics() {
+        let mut config = RunConfig::default();
+        config.example = true;
 
-            if let Some(global_place) = json.get("globalPlace").and_then(|v| v.as_i64())
-                && global_place > 0
-            {
-                parts.push(format!("Global rank: #{}", global_place));
-            }
+        let names = ["Vyrdax"];
+        let outcome = run_part(
+            |notes: &str| Some(names[notes.len()]),
+            "notes",
+            "e2025",
+            1,
+            Part::One,
+            &config,
+        );
+        let PartStatus::Panicked(message) = outcome.status else {
+            panic!("expected a panic, got {outcome:?}");
+        };
+        assert!(message.starts_with("index out of bounds"), "{message}");
+        assert!(message.contains("at src/ec/runner.rs:"), "{message}");
+        assert!(outcome.duration.is_some());
 
-            Some(parts.join(" - "))
-        } else {
-            let mut msg = format!("{}✗ Incorrect answer{}", ANSI_RED, ANSI_RESET);
+        let outcome = run_part(|_| Some(1), "notes", "e2025", 1, Part::Two, &config);
+        assert_eq!(outcome.status, PartStatus::Solved);
+    }
 
-            let length_correct = json
-                .get("lengthCorrect")
-                .and_then(|v| v.as_bool())
-                .unwrap_or(false);
-            if !length_correct {
-                msg.push_str(" (wrong length)");
-            }
+    #[test]
+    fn test_run_part_with_timeout() {
+        let config = RunConfig::default();
+        let timeout = Some(Duration::from_millis(50));
 
-            Some(msg)
+        let outcome = run_part_with_timeout(
+            |input| evaluate(|notes: &str| notes.split(',').next_back(), input),
+            "A,B".to_string(),
+            "e2025",
+            1,
+            Part::One,
+            timeout,
+            &config,
+        );
+        assert_eq!(outcome.answer.as_deref(), Some("B"));
+
+        // Without a timeout, the part still gets the large stack of a worker thread
+        fn depth(n: u64) -> u64 {
+            if n == 0 {
+                0
+            } else {
+                1 + std::hint::black_box(depth(n - 1))
+            }
         }
-    } else {
-        // Fallback to raw response
-        Some(response.to_string())
+        let outcome = run_part_with_timeout(
+            |input| evaluate(|_: &str| Some(depth(200_000)), input),
+            "A,B".to_string(),
+            "e2025",
+            1,
+            Part::One,
+            None,
+            &config,
+        );
+        assert_eq!(outcome.answer.as_deref(), Some("200000"));
+
+        let outcome = run_part_with_timeout(
+            |input| {
+                evaluate(
+                    |_: &str| {
+                        thread::sleep(Duration::from_secs(60));
+                        Some(0)
+                    },
+                    input,
+                )
+            },
+            "A,B".to_string(),
+            "e2025",
+            1,
+            Part::Two,
+            timeout,
+            &config,
+        );
+        assert_eq!(
+            outcome.status,
+            PartStatus::TimedOut(Duration::from_millis(50))
+        );
+        assert!(part_timed_out());
     }
 }
@@ -0,0 +1,294 @@
+use crate::Quest;
+use crate::ec::Part;
+use crate::ec::catalog::{Catalog, EventInfo};
+use crate::ec::client::ClientError;
+use crate::ec::{Event, QuestId};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Initial delay between download attempts after a quest unlocked
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+const MAX_ATTEMPTS: usize = 30;
+
+/// Source of the current time, so that waiting can be tested without actually sleeping
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+    fn sleep(&self, duration: Duration);
+}
+
+/// The real clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+}
+
+impl EventInfo {
+    /// Time at which the quest unlocks, `None` if the start of the event/story is unknown.
+    ///
+    /// Event quests unlock one per weekday (Monday to Friday, UTC) starting with the first quest.
+    /// All quests of a story unlock at once, their parts unlock by solving the previous part.
+    pub fn unlock_time(&self, quest: Quest) -> Option<SystemTime> {
+        let start = self.start?;
+        let days = match self.event {
+            Event::Event(_) => {
+                let first = start / SECONDS_PER_DAY;
+                let mut day = first;
+                for _ in 1..quest.as_u8() {
+                    day += 1;
+                    while is_weekend(day) {
+                        day += 1;
+                    }
+                }
+                day - first
+            }
+            Event::Story(_) => 0,
+        };
+        Some(UNIX_EPOCH + Duration::from_secs(start + days * SECONDS_PER_DAY))
+    }
+
+    /// The first quest that is still locked at the given time, with its unlock time
+    pub fn next_unlock(&self, now: SystemTime) -> Option<(QuestId, SystemTime)> {
+        self.quests()
+            .filter_map(|quest| Some((QuestId::new(self.event, quest), self.unlock_time(quest)?)))
+            .find(|(_, time)| *time > now)
+    }
+}
+
+impl Catalog {
+    /// The next quest of any event/story that unlocks after the given time
+    pub fn next_unlock(&self, now: SystemTime) -> Option<(QuestId, SystemTime)> {
+        self.events()
+            .iter()
+            .filter_map(|info| info.next_unlock(now))
+            .min_by_key(|(_, time)| *time)
+    }
+}
+
+/// Days since the UNIX epoch, which was a Thursday
+fn is_weekend(day: u64) -> bool {
+    (day + 3) % 7 >= 5
+}
+
+/// Sleeps until the given time, printing how long the wait is
+pub fn wait_until(clock: &dyn Clock, time: SystemTime) {
+    if let Ok(remaining) = time.duration_since(clock.now()) {
+        println!(
+            "Waiting {} until {}",
+            format_countdown(remaining),
+            format_utc(time)
+        );
+        clock.sleep(remaining);
+    }
+}
+
+/// Retries `f` with exponential backoff while the server is not ready yet.
+///
+/// Only errors that go away by waiting are retried, e.g. a quest that is not unlocked yet or a server error.
+/// A later part that is locked because the previous part is not solved yet fails at once.
+pub fn retry_with_backoff<T>(
+    clock: &dyn Clock,
+    mut f: impl FnMut() -> Result<T, ClientError>,
+) -> Result<T, ClientError> {
+    let mut backoff = INITIAL_BACKOFF;
+    let mut attempt = 1;
+    loop {
+        match f() {
+            Err(e) if attempt < MAX_ATTEMPTS && is_retryable(&e) => {
+                let delay = match e {
+                    ClientError::RateLimited {
+                        retry_after: Some(retry_after),
+                    } => retry_after,
+                    _ => backoff,
+                };
+                println!("{e}, retrying in {}", format_countdown(delay));
+                clock.sleep(delay);
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+fn is_retryable(e: &ClientError) -> bool {
+    matches!(
+        e,
+        ClientError::NotUnlocked {
+            part: None | Some(Part::One),
+            ..
+        } | ClientError::RateLimited { .. }
+            | ClientError::ServerError(_)
+            | ClientError::ReqwestError(_)
+    )
+}
+
+/// Formats a duration as e.g. `1d 02:03:04`
+pub fn format_countdown(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (days, secs) = (secs / SECONDS_PER_DAY, secs % SECONDS_PER_DAY);
+    let time = format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60);
+    if days > 0 {
+        format!("{days}d {time}")
+    } else {
+        time
+    }
+}
+
+/// Formats a time as e.g. `2025-11-03 23:00 UTC`
+pub fn format_utc(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let (year, month, day) = civil_from_days(secs / SECONDS_PER_DAY);
+    let secs = secs % SECONDS_PER_DAY;
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02} UTC",
+        secs / 3600,
+        secs / 60 % 60
+    )
+}
+
+/// Converts days since the UNIX epoch to a (year, month, day) date
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    // See https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719468;
+    let era = z / 146097;
+    let doe = z % 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::cell::{Cell, RefCell};
+
+    struct FakeClock {
+        now: Cell<SystemTime>,
+        sleeps: RefCell<Vec<Duration>>,
+    }
+
+    impl FakeClock {
+        fn at(secs: u64) -> Self {
+            Self {
+                now: Cell::new(UNIX_EPOCH + Duration::from_secs(secs)),
+                sleeps: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> SystemTime {
+            self.now.get()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.now.set(self.now.get() + duration);
+            self.sleeps.borrow_mut().push(duration);
+        }
+    }
+
+    fn quest(n: u8) -> Quest {
+        n.try_into().unwrap()
+    }
+
+    #[test]
+    fn test_event_unlock_skips_weekends() {
+        let info = Catalog::bundled().get(Event::Event(2025));
+        let unlock = |n| format_utc(info.unlock_time(quest(n)).unwrap());
+
+        assert_eq!(unlock(1), "2025-11-03 23:00 UTC");
+        assert_eq!(unlock(5), "2025-11-07 23:00 UTC");
+        assert_eq!(unlock(6), "2025-11-10 23:00 UTC");
+        assert_eq!(unlock(20), "2025-11-28 23:00 UTC");
+
+        let clock = FakeClock::at(1762210800 + 3600);
+        let (next, _) = info.next_unlock(clock.now()).unwrap();
+        assert_eq!(next.to_string(), "e2025-02");
+    }
+
+    #[test]
+    fn test_story_quests_unlock_together() {
+        let info = Catalog::bundled().get(Event::Story(1));
+        let unlock = |n| format_utc(info.unlock_time(quest(n)).unwrap());
+
+        assert_eq!(unlock(1), "2025-03-31 23:00 UTC");
+        assert_eq!(unlock(3), "2025-03-31 23:00 UTC");
+
+        let (next, _) = info
+            .next_unlock(FakeClock::at(1743462000 - 60).now())
+            .unwrap();
+        assert_eq!(next.to_string(), "s1-01");
+        assert_eq!(info.next_unlock(FakeClock::at(1743462000).now()), None);
+    }
+
+    #[test]
+    fn test_wait_then_retry() {
+        let info = Catalog::bundled().get(Event::Event(2025));
+        let unlock = info.unlock_time(quest(2)).unwrap();
+        let clock = FakeClock::at(1762210800);
+
+        wait_until(&clock, unlock);
+        assert_eq!(clock.now(), unlock);
+
+        let mut attempts = 0;
+        let result = retry_with_backoff(&clock, || {
+            attempts += 1;
+            if attempts < 4 {
+                Err(ClientError::ServerError(503))
+            } else {
+                Ok(attempts)
+            }
+        });
+        assert_eq!(result.unwrap(), 4);
+        assert_eq!(
+            clock.sleeps.borrow()[1..],
+            [1, 2, 4].map(Duration::from_secs)
+        );
+
+        let result: Result<(), _> =
+            retry_with_backoff(&clock, || Err(ClientError::SessionInvalid(401)));
+        assert!(result.is_err());
+        assert_eq!(clock.sleeps.borrow().len(), 4);
+
+        let result: Result<(), _> = retry_with_backoff(&clock, || {
+            Err(ClientError::NotUnlocked {
+                event: Event::Event(2025),
+                quest: quest(2),
+                part: Some(Part::Two),
+            })
+        });
+        assert!(result.is_err());
+        assert_eq!(
+            clock.sleeps.borrow().len(),
+            4,
+            "solving part 1 is not a wait"
+        );
+    }
+
+    #[test]
+    fn test_format_countdown() {
+        assert_eq!(format_countdown(Duration::from_secs(59)), "00:00:59");
+        assert_eq!(
+            format_countdown(Duration::from_secs(SECONDS_PER_DAY + 3723)),
+            "1d 01:02:03"
+        );
+    }
+}
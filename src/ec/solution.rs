@@ -0,0 +1,128 @@
+use crate::ec::alloc::AllocStats;
+use crate::ec::bench::{BenchStats, print_bench_summary};
+use crate::ec::config::{OutputFormat, RunConfig};
+use crate::ec::timings::{Timing, record_timings};
+use crate::ec::{Part, QuestId};
+use std::time::Duration;
+
+/// What happened when running a part
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartStatus {
+    /// The solution produced an answer
+    Solved,
+    /// The solution returned an error or `None`
+    Failed(String),
+    /// The solution panicked, with the panic message and location
+    Panicked(String),
+    /// The solution took longer than its time limit
+    TimedOut(Duration),
+    /// The notes are missing or unreadable
+    NoInput,
+    /// The notes are empty, so the part was not run
+    Skipped,
+}
+
+/// Result of running a single part
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartOutcome {
+    pub part: Part,
+    pub answer: Option<String>,
+    /// Expected answer for the example notes, if one is recorded
+    pub expected: Option<String>,
+    pub duration: Option<Duration>,
+    /// Heap usage, if the `alloc-stats` feature is enabled
+    pub memory: Option<AllocStats>,
+    pub status: PartStatus,
+}
+
+impl PartOutcome {
+    /// An outcome without answer, e.g. for a part that did not run
+    pub fn new(part: Part, status: PartStatus) -> Self {
+        Self {
+            part,
+            answer: None,
+            expected: None,
+            duration: None,
+            memory: None,
+            status,
+        }
+    }
+}
+
+/// Result of running the parts of a quest
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RunOutcome {
+    pub parts: Vec<PartOutcome>,
+    /// Whether `--compare` found a part that got slower
+    pub regressed: bool,
+}
+
+impl RunOutcome {
+    /// Whether any part panicked or timed out
+    pub fn aborted(&self) -> bool {
+        self.parts
+            .iter()
+            .any(|p| matches!(p.status, PartStatus::Panicked(_) | PartStatus::TimedOut(_)))
+    }
+}
+
+/// A quest solution, implemented by the [`solution!`](crate::solution) macro.
+///
+/// Every quest file in `src/bin` registers its solution, so that the `ec` binary can run it in process.
+pub trait Solution {
+    fn id(&self) -> QuestId;
+
+    /// Runs the parts selected by the configuration and prints their answers
+    fn run(&self, config: &RunConfig) -> RunOutcome;
+}
+
+/// Prints the benchmark summary and records the timings of the solved parts
+pub fn finish_run(
+    id: QuestId,
+    parts: Vec<PartOutcome>,
+    bench_results: &[(Part, BenchStats)],
+    config: &RunConfig,
+) -> RunOutcome {
+    if config.format == OutputFormat::Text {
+        print_bench_summary(bench_results);
+        println!();
+    }
+
+    let timings: Vec<_> = parts
+        .iter()
+        .filter(|p| p.status == PartStatus::Solved)
+        .filter_map(|p| {
+            Some(Timing {
+                part: p.part,
+                duration: p.duration?,
+                bench: config.bench,
+            })
+        })
+        .collect();
+    let event = id.event.to_string();
+    let regressed = record_timings(&event, id.quest.as_u8(), &timings, config);
+
+    RunOutcome { parts, regressed }
+}
+
+/// The quest solutions that were compiled into a binary
+#[derive(Default)]
+pub struct Registry {
+    solutions: Vec<&'static dyn Solution>,
+}
+
+impl Registry {
+    pub fn new(mut solutions: Vec<&'static dyn Solution>) -> Self {
+        solutions.sort_by_key(|s| s.id());
+        Self { solutions }
+    }
+
+    /// All solutions, ordered by event and quest
+    pub fn solutions(&self) -> &[&'static dyn Solution] {
+        &self.solutions
+    }
+
+    pub fn get(&self, id: QuestId) -> Option<&'static dyn Solution> {
+        self.solutions.iter().copied().find(|s| s.id() == id)
+    }
+}
@@ -1,68 +1,176 @@
+use crate::ec::paths::relative;
+use crate::ec::{Part, QuestPartId};
 use itertools::Itertools;
-use std::env;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum InputError {
+    #[error("input file {} does not exist, download it with `cargo scaffold {id}`", relative(path).display())]
+    MissingInput { id: QuestPartId, path: PathBuf },
+    #[error("example file {} does not exist, create it with `cargo scaffold {id}`", relative(path).display())]
+    MissingExample { id: QuestPartId, path: PathBuf },
+    #[error("could not read {}: {source}", relative(path).display())]
+    IoError { path: PathBuf, source: io::Error },
+    #[error("could not read the input from stdin: {0}")]
+    StdinError(String),
+}
+
+impl InputError {
+    /// Whether the file does not exist, as opposed to existing but being unreadable
+    pub fn is_missing(&self) -> bool {
+        matches!(
+            self,
+            InputError::MissingInput { .. } | InputError::MissingExample { .. }
+        )
+    }
+}
 
 /// Helper function that reads an input file to a string.
+///
+/// # Panics
+///
+/// If the file cannot be read, see [`try_read_input_file`] for a fallible version.
 #[must_use]
-pub fn read_input_file(event: impl AsRef<str>, quest: u8, part: u8) -> String {
-    let event = event.as_ref();
-    let cwd = env::current_dir().unwrap();
-    let path = cwd
-        .join("inputs")
-        .join("notes")
-        .join(format!("{event}-{quest:02}-{part}.txt"));
-
-    let r = BufReader::new(File::open(path).expect("could not open input file"));
-    r.lines()
-        .map(|l| l.expect("could not read input file"))
-        .join("\n")
+pub fn read_input_file(event: impl AsRef<str>, quest: u8, part: Part) -> String {
+    try_read_input_file(event, quest, part).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Reads the input file of a quest part to a string
+pub fn try_read_input_file(
+    event: impl AsRef<str>,
+    quest: u8,
+    part: Part,
+) -> Result<String, InputError> {
+    let id = quest_part_id(event.as_ref(), quest, part);
+    let path = id.notes_path();
+    if !path.exists() {
+        return Err(InputError::MissingInput { id, path });
+    }
+    read_notes(&path)
 }
 
 /// Helper function that reads an example file to a string.
+///
+/// # Panics
+///
+/// If the file cannot be read, see [`try_read_example_file`] for a fallible version.
 #[must_use]
-pub fn read_example_file(event: impl AsRef<str>, quest: u8, part: u8) -> String {
-    let event = event.as_ref();
-    let cwd = env::current_dir().unwrap();
-    let path = cwd
-        .join("inputs")
-        .join("examples")
-        .join(format!("{event}-{quest:02}-{part}.txt"));
-
-    let r = BufReader::new(File::open(path).expect("could not open example file"));
-    r.lines()
-        .map(|l| l.expect("could not read example file"))
-        .join("\n")
+pub fn read_example_file(event: impl AsRef<str>, quest: u8, part: Part) -> String {
+    try_read_example_file(event, quest, part).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Reads the example file of a quest part to a string
+pub fn try_read_example_file(
+    event: impl AsRef<str>,
+    quest: u8,
+    part: Part,
+) -> Result<String, InputError> {
+    let id = quest_part_id(event.as_ref(), quest, part);
+    let path = id.example_path();
+    if !path.exists() {
+        return Err(InputError::MissingExample { id, path });
+    }
+    read_notes(&path)
+}
+
+/// Reads notes with normalized line endings and without a trailing newline
+pub(crate) fn read_notes(path: &Path) -> Result<String, InputError> {
+    let content = fs::read_to_string(path).map_err(|source| InputError::IoError {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    Ok(content.lines().join("\n"))
+}
+
+pub(crate) fn quest_part_id(event: &str, quest: u8, part: Part) -> QuestPartId {
+    QuestPartId::new(
+        event.parse().expect("invalid event/story"),
+        quest.try_into().expect("invalid quest"),
+        part,
+    )
 }
 
 /// Creates the solution macro for quest binaries
+///
+/// A time limit per part can be given as a [`Duration`](std::time::Duration), e.g.
+/// `solution!("e2025", 1, timeout = Duration::from_secs(10))`.
 #[macro_export]
 macro_rules! solution {
-    ($event:expr, $quest:expr) => {
-        $crate::solution!(@impl $event, $quest, [part_one, 1] [part_two, 2] [part_three, 3]);
+    ($event:expr, $quest:expr $(, timeout = $timeout:expr)?) => {
+        $crate::solution!(@impl $event, $quest, [$($timeout)?], [part_one, One] [part_two, Two] [part_three, Three]);
     };
-    ($event:expr, $quest:expr, 1) => {
-        $crate::solution!(@impl $event, $quest, [part_one, 1]);
+    ($event:expr, $quest:expr, 1 $(, timeout = $timeout:expr)?) => {
+        $crate::solution!(@impl $event, $quest, [$($timeout)?], [part_one, One]);
     };
-    ($event:expr, $quest:expr, 2) => {
-        $crate::solution!(@impl $event, $quest, [part_two, 2]);
+    ($event:expr, $quest:expr, 2 $(, timeout = $timeout:expr)?) => {
+        $crate::solution!(@impl $event, $quest, [$($timeout)?], [part_two, Two]);
     };
-    ($event:expr, $quest:expr, 3) => {
-        $crate::solution!(@impl $event, $quest, [part_three, 3]);
+    ($event:expr, $quest:expr, 3 $(, timeout = $timeout:expr)?) => {
+        $crate::solution!(@impl $event, $quest, [$($timeout)?], [part_three, Three]);
     };
 
-    (@impl $event:expr, $quest:expr, $( [$func:expr, $part:expr] )*) => {
+    (@timeout) => { None };
+    (@timeout $timeout:expr) => { Some($timeout) };
+
+    (@impl $event:expr, $quest:expr, [$($timeout:expr)?], $( [$func:expr, $part:ident] )*) => {
         pub const EVENT: &str = $event;
         pub const QUEST: u8 = $quest;
+        /// Time limit per part, unless one is given with `--timeout` or `EC_TIMEOUT`
+        pub const TIMEOUT: Option<std::time::Duration> = $crate::solution!(@timeout $($timeout)?);
 
-        fn main() {
-            use ec::{run_part, read_input_file};
-            $(
-                let input = read_input_file(EVENT, QUEST, $part);
-                run_part($func, &input, EVENT, QUEST, $part);
-            )*
+        /// Runs this quest, either from its own binary or in process from the `ec` binary
+        pub struct QuestSolution;
+
+        impl $crate::ec::solution::Solution for QuestSolution {
+            fn id(&self) -> $crate::ec::QuestId {
+                $crate::ec::QuestId::new(
+                    EVENT.parse().expect("invalid event/story"),
+                    QUEST.try_into().expect("invalid quest"),
+                )
+            }
 
-            println!();
+            fn run(&self, config: &$crate::RunConfig) -> $crate::ec::solution::RunOutcome {
+                use $crate::Part;
+                let mut parts = Vec::new();
+                let mut bench_results = Vec::new();
+                $(
+                    if config.runs(Part::$part) {
+                        parts.push(match config.read_input(EVENT, QUEST, Part::$part) {
+                            Ok(input) if config.bench => {
+                                let (outcome, stats) = $crate::ec::bench::bench_part($func, &input, Part::$part, config);
+                                bench_results.extend(stats.map(|stats| (Part::$part, stats)));
+                                outcome
+                            }
+                            Ok(input) => $crate::ec::runner::run_part_with_timeout(
+                                |input: &str| $crate::ec::runner::evaluate($func, input),
+                                input,
+                                EVENT,
+                                QUEST,
+                                Part::$part,
+                                config.timeout.or(TIMEOUT),
+                                config,
+                            ),
+                            Err(e) => $crate::ec::runner::report_input_error(Part::$part, &e, config),
+                        });
+                    }
+                )*
+
+                $crate::ec::solution::finish_run(self.id(), parts, &bench_results, config)
+            }
+        }
+
+        // Unused when the quest is compiled into the `ec` binary
+        #[allow(dead_code)]
+        fn main() {
+            use $crate::ec::solution::Solution;
+            let config = $crate::RunConfig::from_env();
+            let outcome = QuestSolution.run(&config);
+            if outcome.regressed || outcome.aborted() {
+                std::process::exit(1);
+            }
         }
     };
 }
@@ -0,0 +1,65 @@
+use crate::ec::client::ClientError;
+use crate::ec::ledger::{Ledger, LedgerError};
+use crate::ec::runner::{ANSI_RED, ANSI_RESET, format_submission_response};
+use crate::ec::{Event, Part};
+use crate::{Client, Quest};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum SubmitError {
+    #[error("Client error: {0}")]
+    ClientError(ClientError),
+    #[error("Ledger error: {0}")]
+    LedgerError(LedgerError),
+    /// The ledger already knows the outcome of this submission
+    #[error("Not submitted: {0}")]
+    Refused(LedgerError),
+    #[error("Submission failed: {0}")]
+    SubmissionFailed(ClientError),
+}
+
+impl SubmitError {
+    /// Actionable advice for the underlying client error, if there is any
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            SubmitError::ClientError(e) | SubmitError::SubmissionFailed(e) => e.hint(),
+            _ => None,
+        }
+    }
+}
+
+/// Submits an answer after checking it against the ledger, then records the response.
+///
+/// Returns the formatted response, including a warning if the submission could not be recorded.
+pub fn submit_answer(
+    event: Event,
+    quest: Quest,
+    part: Part,
+    answer: &str,
+) -> Result<String, SubmitError> {
+    let client = Client::try_new().map_err(SubmitError::ClientError)?;
+    let mut ledger = Ledger::load_default().map_err(SubmitError::LedgerError)?;
+    ledger
+        .check(event, quest, part, client.seed(), answer)
+        .map_err(SubmitError::Refused)?;
+
+    let response = client
+        .submit_answer(event, quest, part, answer)
+        .map_err(SubmitError::SubmissionFailed)?;
+
+    let mut info = format_submission_response(&response);
+    if let Err(e) = ledger.record(event, quest, part, client.seed(), answer, &response) {
+        info.push_str(&format!(
+            " {ANSI_RED}(could not record submission: {e}){ANSI_RESET}"
+        ));
+    }
+    Ok(info)
+}
+
+/// Formats a failed submission like a response, so it can be shown in place of one
+pub fn format_submit_error(e: &SubmitError) -> String {
+    match e.hint() {
+        Some(hint) => format!("{ANSI_RED}✗ {e}{ANSI_RESET} ({hint})"),
+        None => format!("{ANSI_RED}✗ {e}{ANSI_RESET}"),
+    }
+}
@@ -0,0 +1,324 @@
+use crate::ec::Part;
+use crate::ec::config::{OutputFormat, RunConfig};
+use crate::ec::paths::project_root;
+use crate::ec::record_file::{RecordFile, RecordFileError, timestamp};
+use crate::ec::runner::{ANSI_GREEN, ANSI_RED, ANSI_RESET, part_timed_out};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::Command;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+const TIMINGS_FILE: &str = "timings.json";
+
+/// Slowdowns below this are noise, however large they are relative to the baseline
+const MIN_REGRESSION: Duration = Duration::from_micros(50);
+
+/// Runtime of a part at a commit, as recorded in the timings file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimingRecord {
+    pub event: String,
+    pub quest: u8,
+    pub part: Part,
+    pub commit: String,
+    /// Whether this is the median of a benchmark, as opposed to a single run
+    pub bench: bool,
+    pub seconds: f64,
+    /// Seconds since the UNIX epoch
+    pub timestamp: u64,
+}
+
+impl TimingRecord {
+    fn matches(&self, event: &str, quest: u8, part: Part, bench: bool) -> bool {
+        self.event == event && self.quest == quest && self.part == part && self.bench == bench
+    }
+
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs_f64(self.seconds)
+    }
+}
+
+/// A measured runtime of a part, not yet recorded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+    pub part: Part,
+    pub duration: Duration,
+    pub bench: bool,
+}
+
+/// The timing of a part compared with its baseline
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub part: Part,
+    pub baseline_commit: String,
+    pub baseline: Duration,
+    pub current: Duration,
+    pub regressed: bool,
+}
+
+/// Persistent record of the runtimes of every part, keyed by quest, part and git commit
+#[derive(Debug)]
+pub struct Timings {
+    file: RecordFile<TimingRecord>,
+}
+
+impl Timings {
+    /// Loads the timings from `timings.json` in the inputs directory, starting empty if it does not exist yet
+    pub fn load_default() -> Result<Self, RecordFileError> {
+        Ok(Self {
+            file: RecordFile::load_default(TIMINGS_FILE)?,
+        })
+    }
+
+    /// Loads the timings from the given file, starting empty if it does not exist yet
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RecordFileError> {
+        Ok(Self {
+            file: RecordFile::load(path)?,
+        })
+    }
+
+    pub fn records(&self) -> &[TimingRecord] {
+        self.file.records()
+    }
+
+    /// The latest timing of the same kind, from the given commit or else from any other commit than `current_commit`
+    pub fn baseline(
+        &self,
+        event: &str,
+        quest: u8,
+        timing: Timing,
+        current_commit: &str,
+        commit: Option<&str>,
+    ) -> Option<&TimingRecord> {
+        self.records()
+            .iter()
+            .rev()
+            .filter(|r| r.matches(event, quest, timing.part, timing.bench))
+            .find(|r| match commit {
+                Some(commit) => r.commit == commit,
+                None => r.commit != current_commit,
+            })
+    }
+
+    /// Replaces the timing of the part at the commit and writes the file back to disk
+    pub fn record(
+        &mut self,
+        event: &str,
+        quest: u8,
+        timing: Timing,
+        commit: &str,
+    ) -> Result<(), RecordFileError> {
+        let record = TimingRecord {
+            event: event.to_string(),
+            quest,
+            part: timing.part,
+            commit: commit.to_string(),
+            bench: timing.bench,
+            seconds: timing.duration.as_secs_f64(),
+            timestamp: timestamp(),
+        };
+        self.file.update(|records| {
+            records.retain(|r| {
+                !(r.matches(event, quest, timing.part, timing.bench) && r.commit == commit)
+            });
+            records.push(record);
+        })
+    }
+}
+
+/// Compares a timing with its baseline, flagging it if it is more than `threshold_percent` slower
+pub fn compare(timing: Timing, baseline: &TimingRecord, threshold_percent: f64) -> Comparison {
+    let allowed = baseline.duration().as_secs_f64() * (1.0 + threshold_percent / 100.0);
+    let regressed = timing.duration.as_secs_f64() > allowed
+        && timing.duration.saturating_sub(baseline.duration()) > MIN_REGRESSION;
+    Comparison {
+        part: timing.part,
+        baseline_commit: baseline.commit.clone(),
+        baseline: baseline.duration(),
+        current: timing.duration,
+        regressed,
+    }
+}
+
+/// The short hash of the checked out commit, marked `-dirty` if tracked files have uncommitted changes.
+///
+/// Looked up once per process, as `run-all` records the timings of every quest.
+pub fn current_commit() -> &'static str {
+    static COMMIT: OnceLock<String> = OnceLock::new();
+    COMMIT.get_or_init(|| find_commit(&project_root()))
+}
+
+/// Untracked files do not count as uncommitted changes, as e.g. new notes would otherwise mark every run dirty
+fn find_commit(dir: &Path) -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(commit)
+            if git(&["status", "--porcelain", "--untracked-files=no"])
+                .is_some_and(|s| !s.is_empty()) =>
+        {
+            format!("{commit}-dirty")
+        }
+        Some(commit) => commit,
+        None => "unknown".to_string(),
+    }
+}
+
+/// Records the timings of a quest run and compares them with their baselines if requested.
+///
+/// Only runs on the downloaded notes are recorded, and nothing after a part timed out, as its thread
+/// still running slows down everything else. Returns whether any part regressed.
+pub fn record_timings(event: &str, quest: u8, timings: &[Timing], config: &RunConfig) -> bool {
+    let timings: Vec<_> = timings
+        .iter()
+        .filter(|t| config.uses_own_notes(t.part))
+        .copied()
+        .collect();
+    if timings.is_empty() {
+        return false;
+    }
+    if part_timed_out() {
+        report(
+            config,
+            "Timings not recorded or compared, a part that timed out is still running",
+        );
+        return false;
+    }
+
+    let mut stored = match Timings::load_default() {
+        Ok(stored) => stored,
+        Err(e) => {
+            eprintln!("{ANSI_RED}Could not load timings: {e}{ANSI_RESET}");
+            return config.compare;
+        }
+    };
+    let commit = current_commit();
+
+    let mut comparisons = Vec::new();
+    for timing in &timings {
+        if config.compare {
+            match stored.baseline(event, quest, *timing, commit, config.baseline.as_deref()) {
+                Some(baseline) => comparisons.push(compare(*timing, baseline, config.threshold)),
+                None => report(config, &format!("Part {}: no baseline", timing.part)),
+            }
+        }
+        if let Err(e) = stored.record(event, quest, *timing, commit) {
+            eprintln!("{ANSI_RED}Could not record timings: {e}{ANSI_RESET}");
+        }
+    }
+
+    for c in &comparisons {
+        let change = (c.current.as_secs_f64() / c.baseline.as_secs_f64() - 1.0) * 100.0;
+        let verdict = if c.regressed {
+            format!("{ANSI_RED}✗ slower{ANSI_RESET}")
+        } else {
+            format!("{ANSI_GREEN}✓{ANSI_RESET}")
+        };
+        report(
+            config,
+            &format!(
+                "Part {}: {:.2?} at {} -> {:.2?} ({change:+.0}%) {verdict}",
+                c.part, c.baseline, c.baseline_commit, c.current
+            ),
+        );
+    }
+
+    comparisons.iter().any(|c| c.regressed)
+}
+
+/// Prints to stdout, or to stderr if stdout has to stay machine readable
+fn report(config: &RunConfig, line: &str) {
+    match config.format {
+        OutputFormat::Text => println!("{line}"),
+        OutputFormat::Json => eprintln!("{line}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn timing(micros: u64) -> Timing {
+        Timing {
+            part: Part::One,
+            duration: Duration::from_micros(micros),
+            bench: true,
+        }
+    }
+
+    #[test]
+    fn test_baseline_and_regressions() {
+        let path = std::env::temp_dir().join(format!("ec-timings-{}.json", std::process::id()));
+        let mut timings = Timings::load(&path).unwrap();
+        timings.record("e2025", 1, timing(1000), "aaa").unwrap();
+        timings.record("e2025", 1, timing(900), "bbb").unwrap();
+        timings.record("e2025", 1, timing(2000), "bbb").unwrap();
+
+        let timings = Timings::load(&path).unwrap();
+        assert_eq!(timings.records().len(), 2);
+
+        let baseline = timings
+            .baseline("e2025", 1, timing(0), "bbb", None)
+            .unwrap();
+        assert_eq!(baseline.commit, "aaa");
+        assert!(compare(timing(1300), baseline, 20.0).regressed);
+        assert!(!compare(timing(1100), baseline, 20.0).regressed);
+
+        let baseline = timings
+            .baseline("e2025", 1, timing(0), "ccc", Some("bbb"))
+            .unwrap();
+        assert!(
+            !compare(timing(2040), baseline, 1.0).regressed,
+            "below the noise floor"
+        );
+        assert!(
+            timings
+                .baseline("e2025", 2, timing(0), "ccc", None)
+                .is_none()
+        );
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_find_commit() {
+        let dir = std::env::temp_dir().join(format!("ec-git-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .args(["-c", "user.name=ec", "-c", "user.email=ec@example.com"])
+                .args(args)
+                .current_dir(&dir)
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {args:?}");
+        };
+        assert_eq!(find_commit(&dir), "unknown");
+
+        git(&["init", "--quiet"]);
+        fs::write(dir.join("solution.rs"), "fn main() {}").unwrap();
+        git(&["add", "solution.rs"]);
+        git(&["commit", "--quiet", "--message", "Solve"]);
+        let commit = find_commit(&dir);
+        assert!(!commit.is_empty() && commit != "unknown", "{commit}");
+
+        fs::write(dir.join("notes.txt"), "untracked").unwrap();
+        assert_eq!(find_commit(&dir), commit);
+
+        fs::write(dir.join("solution.rs"), "fn main() { todo!() }").unwrap();
+        assert_eq!(find_commit(&dir), format!("{commit}-dirty"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
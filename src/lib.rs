@@ -1,39 +1,49 @@
 pub mod ec;
 
 use crate::ec::Event;
+use crate::ec::catalog::Catalog;
 use crate::ec::client::ClientError;
-pub use ec::{Client, Quest, read_example_file, read_input_file, run_part};
+use crate::ec::html::{Example, extract_example, html_to_markdown, html_to_terminal};
+use crate::ec::id::{QuestId, QuestPartId};
+use crate::ec::ledger::Ledger;
+use crate::ec::panics::catch_panic;
+use crate::ec::paths::{project_root, relative};
+use crate::ec::runner::{ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET};
+use crate::ec::schedule::{
+    Clock, SystemClock, format_countdown, format_utc, retry_with_backoff, wait_until,
+};
+use crate::ec::solution::{PartStatus, Registry};
+use crate::ec::submit::submit_answer;
+pub use ec::{
+    Client, Part, Quest, RunConfig, read_example_file, read_input_file, run_part,
+    try_read_example_file, try_read_input_file,
+};
+use itertools::Itertools;
 use std::error::Error;
 use std::fs;
-use std::path::PathBuf;
 use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
 
 /// Determines which quest to scaffold based on existing input files
-pub fn determine_next_quest(event: Event) -> u8 {
-    let inputs_dir = PathBuf::from("inputs/notes");
-
-    for quest in 1..=25 {
-        for part in 1..=3 {
-            let file_path = inputs_dir.join(format!("{event}-{quest:02}-{part}.txt"));
-            if !file_path.exists() {
+pub fn determine_next_quest(event: Event, catalog: &Catalog) -> Quest {
+    for quest in catalog.get(event).quests() {
+        for part in Part::all() {
+            if !QuestPartId::new(event, quest, part).notes_path().exists() {
                 return quest;
             }
         }
     }
 
     // If all exist, default to 1
-    1
+    Quest::FIRST
 }
 
 /// Determines which quest to solve based on existing input files
-pub fn determine_current_quest(event: Event) -> u8 {
-    let inputs_dir = PathBuf::from("inputs/notes");
-
-    let mut last_quest = 1;
-    'outer: for quest in 1..=25 {
-        for part in 1..=3 {
-            let file_path = inputs_dir.join(format!("{event}-{quest:02}-{part}.txt"));
-            if !file_path.exists() {
+pub fn determine_current_quest(event: Event, catalog: &Catalog) -> Quest {
+    let mut last_quest = Quest::FIRST;
+    'outer: for quest in catalog.get(event).quests() {
+        for part in Part::all() {
+            if !QuestPartId::new(event, quest, part).notes_path().exists() {
                 break 'outer;
             } else {
                 last_quest = quest;
@@ -45,125 +55,634 @@ pub fn determine_current_quest(event: Event) -> u8 {
 }
 
 /// Determines which part to scaffold based on existing input files
-pub fn determine_next_part(event: Event, quest: Quest) -> u8 {
-    let inputs_dir = PathBuf::from("inputs/notes");
-
-    for part in 1..=3 {
-        let file_path = inputs_dir.join(format!("{event}-{quest:02}-{part}.txt"));
-        if !file_path.exists() {
+pub fn determine_next_part(id: QuestId) -> Part {
+    for part in Part::all() {
+        if !id.part(part).notes_path().exists() {
             return part;
         }
     }
 
     // If all exist, default to 1
-    1
+    Part::One
+}
+
+/// Uses `EC_EVENT` if no event/story was given
+fn resolve_event(event: Option<Event>) -> Result<Event, Box<dyn Error>> {
+    match event {
+        Some(event) => Ok(event),
+        None => Ok(std::env::var("EC_EVENT")
+            .map_err(|_| ClientError::EventNotConfigured)?
+            .parse()?),
+    }
 }
 
-/// Creates the directory structure and files for a quest
+/// Creates the directory structure and files for a quest.
+///
+/// With `wait`, sleeps until the quest unlocks and retries the download until it succeeds.
 pub fn scaffold_quest(
-    event: Option<String>,
-    quest: Option<u8>,
-    part: Option<u8>,
+    event: Option<Event>,
+    quest: Option<Quest>,
+    part: Option<Part>,
+    wait: bool,
 ) -> Result<(), Box<dyn Error>> {
-    let event: Event = event
-        .or_else(|| std::env::var("EC_EVENT").ok())
-        .ok_or(ClientError::EventNotConfigured)?
-        .parse()?;
-    let quest: Quest = quest
-        .unwrap_or_else(|| determine_next_quest(event))
-        .try_into()?;
-    let part = part.unwrap_or_else(|| determine_next_part(event, quest));
-    println!("Scaffolding: {event}-{quest:02}-{part}");
+    let clock = SystemClock;
+    scaffold_quest_with_clock(event, quest, part, wait.then_some(&clock as &dyn Clock))
+}
+
+/// Like [`scaffold_quest`], waiting for the quest to unlock with the given clock if there is one
+pub fn scaffold_quest_with_clock(
+    event: Option<Event>,
+    quest: Option<Quest>,
+    part: Option<Part>,
+    wait: Option<&dyn Clock>,
+) -> Result<(), Box<dyn Error>> {
+    let event = resolve_event(event)?;
+    let client = Client::try_new()?;
+    let catalog = Catalog::bundled();
+    let quest_id = QuestId::new(
+        event,
+        quest.unwrap_or_else(|| determine_next_quest(event, &catalog)),
+    );
+    catalog.validate(quest_id)?;
+    let id = quest_id.part(part.unwrap_or_else(|| determine_next_part(quest_id)));
+    println!("Scaffolding: {id}");
 
     // Try to download input first - if this fails, don't create any files
-    let input_content = download_input(event, quest, part)?;
+    let (input_content, example) = match wait {
+        Some(clock) => {
+            if let Some(unlock) = catalog.get(event).unlock_time(quest_id.quest) {
+                wait_until(clock, unlock);
+            }
+            retry_with_backoff(clock, || download_part(&client, id))?
+        }
+        None => download_part(&client, id)?,
+    };
 
     // Create directories
-    fs::create_dir_all("src/bin")?;
-    fs::create_dir_all("inputs/notes")?;
-    fs::create_dir_all("inputs/examples")?;
+    for path in [quest_id.bin_path(), id.notes_path(), id.example_path()] {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+    }
 
     // Create quest file from template if it doesn't exist
-    let quest_file = PathBuf::from(format!("src/bin/quest_{event}_{quest:02}.rs"));
+    let quest_file = quest_id.bin_path();
     if !quest_file.exists() {
         let template = include_str!("./template.txt");
         let content = template
             .replace("%EVENT%", &format!("\"{event}\""))
-            .replace("%QUEST_NUMBER%", &quest.as_u8().to_string());
+            .replace("%QUEST_NUMBER%", &quest_id.quest.as_u8().to_string());
         fs::write(&quest_file, content)?;
-        println!("Created [\x1b[0;32m {} \x1b[0m]", quest_file.display());
+        println!(
+            "Created [\x1b[0;32m {} \x1b[0m]",
+            relative(&quest_file).display()
+        );
     } else {
-        println!("Quest file already exists: {}", quest_file.display());
+        println!(
+            "Quest file already exists: {}",
+            relative(&quest_file).display()
+        );
+    }
+
+    // Fill in the expected example answer of the new part
+    if let Some(answer) = example.as_ref().and_then(|e| e.answer.as_ref()) {
+        let content = fs::read_to_string(&quest_file)?;
+        if let Some(content) = fill_expected_answer(&content, id.part, answer) {
+            fs::write(&quest_file, content)?;
+            println!(
+                "Expecting example answer {answer:?} in test for part {}",
+                id.part
+            );
+        }
     }
 
     // Create input file
-    let input_file = PathBuf::from(format!("inputs/notes/{event}-{quest:02}-{part}.txt"));
+    let input_file = id.notes_path();
     if !input_file.exists() {
         fs::write(&input_file, input_content)?;
-        println!("Created [\x1b[0;32m {} \x1b[0m]", input_file.display());
+        println!(
+            "Created [\x1b[0;32m {} \x1b[0m]",
+            relative(&input_file).display()
+        );
     } else {
-        println!("Input file already exists: {}", input_file.display());
+        println!(
+            "Input file already exists: {}",
+            relative(&input_file).display()
+        );
     }
 
     // Create example file
-    let example_file = PathBuf::from(format!("inputs/examples/{event}-{quest:02}-{part}.txt"));
+    let example_file = id.example_path();
     if !example_file.exists() {
-        fs::write(&example_file, "")?;
-        println!("Created [\x1b[0;32m {} \x1b[0m]", example_file.display());
+        match &example {
+            Some(example) => fs::write(&example_file, &example.notes)?,
+            None => {
+                println!("No example found in the description, fill the example file yourself");
+                fs::write(&example_file, "")?;
+            }
+        }
+        println!(
+            "Created [\x1b[0;32m {} \x1b[0m]",
+            relative(&example_file).display()
+        );
     } else {
-        println!("Example file already exists: {}", example_file.display());
+        println!(
+            "Example file already exists: {}",
+            relative(&example_file).display()
+        );
+    }
+
+    // Record the expected example answer for `--example` runs
+    let expected_file = id.expected_path();
+    if let Some(answer) = example.as_ref().and_then(|e| e.answer.as_ref())
+        && !expected_file.exists()
+    {
+        fs::write(&expected_file, answer)?;
+        println!(
+            "Created [\x1b[0;32m {} \x1b[0m]",
+            relative(&expected_file).display()
+        );
     }
 
-    println!("\nScaffolding complete for Quest {quest} Part {part}");
+    println!(
+        "\nScaffolding complete for Quest {} Part {}",
+        quest_id.quest, id.part
+    );
     Ok(())
 }
 
-/// Downloads and decrypts input for a quest part
-fn download_input(event: Event, quest: Quest, part: u8) -> Result<String, Box<dyn Error>> {
-    let client = Client::try_new()?;
+/// Downloads and decrypts input for a quest part, along with the example from its description
+fn download_part(
+    client: &Client,
+    id: QuestPartId,
+) -> Result<(String, Option<Example>), ClientError> {
+    let (event, quest, part) = (id.event(), id.quest.quest, id.part);
     let input = client.fetch_and_decrypt_input(event, quest, part)?;
-    Ok(input)
+
+    // The example is a nice-to-have, so failing to get it must not stop scaffolding
+    let example = match client.fetch_description(event, quest, part) {
+        Ok(html) => extract_example(&html),
+        Err(e) => {
+            println!("Could not fetch the description: {e}");
+            None
+        }
+    };
+
+    Ok((input, example))
+}
+
+/// Replaces the placeholder assertion in the test of the given part and enables the test.
+///
+/// Returns `None` if the test was already filled in.
+fn fill_expected_answer(content: &str, part: Part, answer: &str) -> Option<String> {
+    let name = match part {
+        Part::One => "test_part_one",
+        Part::Two => "test_part_two",
+        Part::Three => "test_part_three",
+    };
+    let fn_start = content.find(&format!("fn {name}()"))?;
+    let fn_end = content[fn_start + 1..]
+        .find("\n    fn ")
+        .map_or(content.len(), |i| fn_start + 1 + i);
+
+    const PLACEHOLDER: &str = "assert_eq!(result, \"\");";
+    let assert_start = fn_start + content[fn_start..fn_end].find(PLACEHOLDER)?;
+
+    // Drop the #[ignore] from the attributes directly above the function
+    let attributes_start = content[..fn_start].rfind("#[test]").unwrap_or(fn_start);
+    let attributes = content[attributes_start..fn_start].replacen("#[ignore]\n    ", "", 1);
+
+    Some(format!(
+        "{}{attributes}{}assert_eq!(result, {answer:?});{}",
+        &content[..attributes_start],
+        &content[fn_start..assert_start],
+        &content[assert_start + PLACEHOLDER.len()..]
+    ))
+}
+
+/// Fetches the description of the unlocked parts of a quest and prints it
+pub fn read_quest(
+    event: Option<Event>,
+    quest: Option<Quest>,
+    part: Option<Part>,
+    save: bool,
+) -> Result<(), Box<dyn Error>> {
+    let event = resolve_event(event)?;
+    let client = Client::try_new()?;
+    let catalog = Catalog::bundled();
+    let id = QuestId::new(
+        event,
+        quest.unwrap_or_else(|| determine_current_quest(event, &catalog)),
+    );
+    catalog.validate(id)?;
+    let quest = id.quest;
+
+    let mut descriptions = Vec::new();
+    let parts: Vec<Part> = part.map_or_else(|| Part::all().collect(), |p| vec![p]);
+    for p in parts {
+        match client.fetch_description(event, quest, p) {
+            Ok(html) => descriptions.push(html),
+            // Without an explicit part, show everything that is unlocked so far
+            Err(ClientError::NotUnlocked { .. }) if part.is_none() && !descriptions.is_empty() => {
+                break;
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    for html in &descriptions {
+        println!("{}", html_to_terminal(html));
+    }
+
+    if save {
+        let file = match part {
+            Some(p) => id.part(p).description_path(),
+            None => id.description_path(),
+        };
+        if let Some(dir) = file.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let markdown = descriptions
+            .iter()
+            .map(|html| html_to_markdown(html))
+            .join("\n");
+        fs::write(&file, markdown)?;
+        println!("Created [\x1b[0;32m {} \x1b[0m]", relative(&file).display());
+    }
+
+    Ok(())
+}
+
+/// Prints when the next quest unlocks
+pub fn print_next_unlock(event: Option<Event>) -> Result<(), Box<dyn Error>> {
+    let event = event.or_else(|| std::env::var("EC_EVENT").ok()?.parse().ok());
+    let catalog = Catalog::bundled();
+
+    let now = SystemClock.now();
+    let next = match event {
+        Some(event) => catalog.get(event).next_unlock(now),
+        None => catalog.next_unlock(now),
+    };
+
+    match next {
+        Some((id, time)) => println!(
+            "{id} unlocks in {} ({})",
+            format_countdown(time.duration_since(now).unwrap_or_default()),
+            format_utc(time)
+        ),
+        None => match event {
+            Some(event) => println!("All quests of {event} are unlocked"),
+            None => println!("No upcoming quests known"),
+        },
+    }
+
+    Ok(())
+}
+
+/// Submits an answer that was computed outside of a quest binary
+pub fn submit_quest(
+    event: Option<Event>,
+    quest: Quest,
+    part: Part,
+    answer: &str,
+) -> Result<(), Box<dyn Error>> {
+    let event = resolve_event(event)?;
+    let id = QuestId::new(event, quest);
+    Catalog::bundled().validate(id)?;
+
+    let answer = answer.trim_end();
+    if answer.is_empty() {
+        return Err("Answer must not be empty".into());
+    }
+
+    let info = submit_answer(event, quest, part, answer)?;
+    println!(
+        "{}: {ANSI_BOLD}{answer}{ANSI_RESET} - {info}",
+        id.part(part)
+    );
+    Ok(())
+}
+
+/// How [`solve_quest`] runs the quest
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SolveOptions {
+    /// Submit the answer of the given part
+    pub submit: bool,
+    /// Run on the example notes, never submits
+    pub example: bool,
+    /// Report timing statistics of repeated runs, never submits
+    pub bench: bool,
+    /// Fail if a part got slower than its recorded baseline
+    pub compare: bool,
+    /// Time limit per part, overriding `EC_TIMEOUT` and the timeout of the quest
+    pub timeout: Option<Duration>,
+    /// Run the separate quest binary with cargo, even if the solution is registered
+    pub bin: bool,
 }
 
-/// Runs a quest solution
+impl SolveOptions {
+    /// The quest binary configuration for these options
+    fn run_config(&self, part: Option<Part>) -> Result<RunConfig, Box<dyn Error>> {
+        let mut config = RunConfig::default();
+        config.example = self.example;
+        config.bench = self.bench;
+        config.compare = self.compare;
+        config.timeout = self.timeout.or(config.timeout);
+        if self.example || self.bench {
+            config.parts.extend(part);
+        } else if self.submit {
+            config.submit = Some(part.ok_or("Must specify a part number to submit")?);
+        }
+        Ok(config)
+    }
+
+    /// The same configuration as command line arguments of the quest binary
+    fn args(&self, part: Option<Part>) -> Result<Vec<String>, Box<dyn Error>> {
+        let config = self.run_config(part)?;
+        let mut args = Vec::new();
+        if config.example {
+            args.push("--example".to_string());
+        }
+        if config.bench {
+            args.push("--bench".to_string());
+        }
+        if config.compare {
+            args.push("--compare".to_string());
+        }
+        if let Some(timeout) = self.timeout {
+            args.push("--timeout".to_string());
+            args.push(format!("{}s", timeout.as_secs_f64()));
+        }
+        if !config.parts.is_empty() {
+            args.push("--parts".to_string());
+            args.push(config.parts.iter().join(","));
+        }
+        if let Some(part) = config.submit {
+            args.push("--submit".to_string());
+            args.push(part.to_string());
+        }
+        Ok(args)
+    }
+}
+
+/// Runs a quest solution.
+///
+/// Solutions in the registry run in process, all others with `cargo run` of their quest binary.
 pub fn solve_quest(
-    event: Option<String>,
-    quest: Option<u8>,
-    part: Option<u8>,
-    submit: bool,
+    event: Option<Event>,
+    quest: Option<Quest>,
+    part: Option<Part>,
+    options: SolveOptions,
+    registry: &Registry,
 ) -> Result<(), Box<dyn Error>> {
-    let event: Event = event
-        .or_else(|| std::env::var("EC_EVENT").ok())
-        .ok_or(ClientError::EventNotConfigured)?
-        .parse()?;
-    let quest: Quest = quest
-        .unwrap_or_else(|| determine_current_quest(event))
-        .try_into()?;
-    println!("Solving: {event}-{quest:02}-{part:?}");
-
-    let mut cmd = Command::new("cargo");
-    cmd.arg("run")
-        .arg("--release")
-        .arg("--bin")
-        .arg(format!("quest_{event}_{quest:02}"))
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit());
+    let event = resolve_event(event)?;
+    let catalog = Catalog::bundled();
+    let id = QuestId::new(
+        event,
+        quest.unwrap_or_else(|| determine_current_quest(event, &catalog)),
+    );
+    catalog.validate(id)?;
+    let mode = match (options.example, options.bench) {
+        (true, true) => " (example, bench)",
+        (true, false) => " (example)",
+        (false, true) => " (bench)",
+        (false, false) => "",
+    };
+    match part {
+        Some(part) => println!("Solving: {}{mode}", id.part(part)),
+        None => println!("Solving: {id}{mode}"),
+    }
 
-    // Add -- separator before custom args
-    if submit {
-        if let Some(p) = part {
-            cmd.arg("--");
-            cmd.arg("--submit");
-            cmd.arg(p.to_string());
-        } else {
-            return Err("Must specify a part number to submit".into());
+    if let Some(solution) = registry.get(id).filter(|_| !options.bin) {
+        let outcome = solution.run(&options.run_config(part)?);
+        if outcome.aborted() {
+            return Err("Some parts panicked or timed out".into());
         }
+        if outcome.regressed {
+            return Err("Some parts got slower".into());
+        }
+        return Ok(());
     }
 
-    let status = cmd.status()?;
+    let mut command = Command::new("cargo");
+    command
+        .current_dir(project_root())
+        .arg("run")
+        .arg("--release");
+    if cfg!(feature = "alloc-stats") {
+        command.args(["--features", "alloc-stats"]);
+    }
+    let status = command
+        .arg("--bin")
+        .arg(id.bin_name())
+        .arg("--")
+        .args(options.args(part)?)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()?;
     if !status.success() {
         return Err("Quest execution failed".into());
     }
 
     Ok(())
 }
+
+/// A row of the [`run_all`] summary table
+struct SummaryRow {
+    quest: QuestId,
+    part: Option<Part>,
+    answer: String,
+    duration: Option<Duration>,
+    status: String,
+    failed: bool,
+}
+
+/// Runs every registered solution of an event in process and prints a summary table.
+///
+/// A quest that panics is reported and does not stop the others.
+/// Answers are checked against the expected example answers or the correct answers in the submission ledger.
+/// The seed of the ledger entries is taken from the client, i.e. from `EC_SEED` or the API.
+pub fn run_all(
+    event: Option<Event>,
+    example: bool,
+    registry: &Registry,
+) -> Result<(), Box<dyn Error>> {
+    let event = resolve_event(event)?;
+    let solutions: Vec<_> = registry
+        .solutions()
+        .iter()
+        .filter(|s| s.id().event == event)
+        .collect();
+    if registry.solutions().is_empty() && !cfg!(feature = "solutions") {
+        return Err("ec was built without the `solutions` feature, use `cargo run-all`".into());
+    }
+    if solutions.is_empty() {
+        return Err(format!("No solutions for {event} in src/bin").into());
+    }
+
+    let ledger = Ledger::load_default()?;
+    // Answers for the own notes are checked against the ledger, which is keyed by seed
+    let seed = if example {
+        None
+    } else {
+        // Only a missing seed needs a client, which fetches it with the session
+        match Client::seed_from_env().or_else(|_| Client::try_new().map(|client| client.seed())) {
+            Ok(seed) => Some(seed),
+            Err(e) => {
+                println!(
+                    "{ANSI_RED}No seed ({e}), answers are not checked against the submission ledger{ANSI_RESET}\n"
+                );
+                None
+            }
+        }
+    };
+    let mut config = RunConfig::default();
+    config.example = example;
+
+    let timer = Instant::now();
+    let mut rows = Vec::new();
+    for solution in solutions {
+        let id = solution.id();
+        println!("{ANSI_BOLD}{id}{ANSI_RESET}");
+        let outcome = match catch_panic(|| solution.run(&config)) {
+            Ok(outcome) => outcome,
+            Err(caught) => {
+                println!("{ANSI_RED}Panicked: {caught}{ANSI_RESET}\n");
+                rows.push(SummaryRow {
+                    quest: id,
+                    part: None,
+                    answer: "-".to_string(),
+                    duration: None,
+                    status: format!("panicked: {caught}"),
+                    failed: true,
+                });
+                continue;
+            }
+        };
+
+        for p in outcome.parts {
+            let known = match (&p.expected, seed) {
+                (Some(expected), _) => Some(expected.as_str()),
+                (None, Some(seed)) if !example => {
+                    ledger.correct_answer(event, id.quest, p.part, seed)
+                }
+                _ => None,
+            };
+            let (status, failed) = match (&p.status, &p.answer, known) {
+                (PartStatus::Solved, Some(answer), Some(known)) if answer == known => {
+                    ("correct".to_string(), false)
+                }
+                (PartStatus::Solved, _, Some(known)) => (format!("wrong, expected {known}"), true),
+                (PartStatus::Solved, _, None) if seed.is_none() && !example => {
+                    ("solved, unchecked".to_string(), false)
+                }
+                (PartStatus::Solved, _, None) => ("solved".to_string(), false),
+                (PartStatus::Failed(e), _, _) => (format!("failed: {e}"), true),
+                (PartStatus::Panicked(e), _, _) => (format!("panicked: {e}"), true),
+                (PartStatus::TimedOut(timeout), _, _) => {
+                    (format!("timed out after {timeout:?}"), true)
+                }
+                (PartStatus::NoInput, _, _) => ("no input".to_string(), false),
+                (PartStatus::Skipped, _, _) => ("empty input".to_string(), false),
+            };
+            let answer = match p.answer {
+                Some(answer) if answer.contains('\n') => "(multiline)".to_string(),
+                Some(answer) => answer,
+                None => "-".to_string(),
+            };
+            rows.push(SummaryRow {
+                quest: id,
+                part: Some(p.part),
+                answer,
+                duration: p.duration,
+                status,
+                failed,
+            });
+        }
+    }
+    let elapsed = timer.elapsed();
+
+    print_summary(&rows);
+    let total: Duration = rows.iter().filter_map(|r| r.duration).sum();
+    println!("Total: {total:.2?} in the solutions, {elapsed:.2?} overall");
+
+    let failures = rows.iter().filter(|r| r.failed).count();
+    if failures > 0 {
+        return Err(format!("{failures} of {} parts failed", rows.len()).into());
+    }
+    Ok(())
+}
+
+fn print_summary(rows: &[SummaryRow]) {
+    let answer_width = rows
+        .iter()
+        .map(|r| r.answer.chars().count())
+        .chain([6])
+        .max()
+        .unwrap_or_default();
+    println!(
+        "{ANSI_BOLD}{:<9} | Part | {:<answer_width$} | {:>10} | Status{ANSI_RESET}",
+        "Quest", "Answer", "Duration"
+    );
+    for row in rows {
+        let part = row.part.map_or("-".to_string(), |p| p.to_string());
+        let duration = row.duration.map_or("-".to_string(), |d| format!("{d:.2?}"));
+        let color = match (row.failed, row.duration) {
+            (true, _) => ANSI_RED,
+            (false, Some(_)) => ANSI_GREEN,
+            (false, None) => "",
+        };
+        println!(
+            "{:<9} | {part:>4} | {:<answer_width$} | {duration:>10} | {color}{}{ANSI_RESET}",
+            row.quest.to_string(),
+            row.answer,
+            row.status
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ec::solution::{PartOutcome, RunOutcome, Solution};
+
+    struct Panicking;
+
+    impl Solution for Panicking {
+        fn id(&self) -> QuestId {
+            "e2025-01".parse().unwrap()
+        }
+
+        fn run(&self, _: &RunConfig) -> RunOutcome {
+            panic!("oops");
+        }
+    }
+
+    struct Solved;
+
+    impl Solution for Solved {
+        fn id(&self) -> QuestId {
+            "e2025-02".parse().unwrap()
+        }
+
+        fn run(&self, _: &RunConfig) -> RunOutcome {
+            let mut part = PartOutcome::new(Part::One, PartStatus::Solved);
+            part.answer = Some("42".to_string());
+            RunOutcome {
+                parts: vec![part],
+                regressed: false,
+            }
+        }
+    }
+
+    #[test]
+    fn test_run_all_survives_panics() {
+        let registry = Registry::new(vec![&Solved, &Panicking]);
+        let result = run_all(Some("e2025".parse().unwrap()), true, &registry);
+        assert_eq!(result.unwrap_err().to_string(), "1 of 2 parts failed");
+
+        let registry = Registry::new(vec![&Solved]);
+        assert!(run_all(Some("e2025".parse().unwrap()), true, &registry).is_ok());
+        assert!(run_all(Some("e2024".parse().unwrap()), true, &registry).is_err());
+    }
+}
@@ -1,5 +1,18 @@
 use clap::{Parser, Subcommand};
-use ec::{scaffold_quest, solve_quest};
+use ec::ec::client::ClientError;
+use ec::ec::config::parse_duration;
+use ec::ec::solution::Registry;
+use ec::ec::submit::SubmitError;
+use ec::ec::{Event, QuestId, QuestPartId};
+use ec::{
+    Part, Quest, SolveOptions, print_next_unlock, read_quest, run_all, scaffold_quest, solve_quest,
+    submit_quest,
+};
+use std::io::Read;
+use std::str::FromStr;
+use std::time::Duration;
+
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
 
 #[derive(Parser)]
 #[command(name = "Everybody Codes")]
@@ -15,51 +28,209 @@ enum Commands {
     Scaffold {
         /// Event/Story
         #[clap(short, long)]
-        event: Option<String>,
-        /// Quest number (1-20)
-        quest: Option<u8>,
+        event: Option<Event>,
+        /// Quest number or id, e.g. e2025-01 or e2025-01-2
+        quest: Option<QuestArg>,
         /// Part number (1-3), defaults to next unsolved part
-        part: Option<u8>,
+        part: Option<Part>,
+        /// Wait until the quest unlocks, then retry downloading until it is available
+        #[clap(short, long)]
+        wait: bool,
+    },
+    /// Show when the next quest unlocks
+    Next {
+        /// Event/Story, defaults to the next unlock of any known event
+        #[clap(short, long)]
+        event: Option<Event>,
+    },
+    /// Read the description of a quest
+    Read {
+        /// Event/Story
+        #[clap(short, long)]
+        event: Option<Event>,
+        /// Quest number or id, e.g. e2025-01 or e2025-01-2
+        quest: Option<QuestArg>,
+        /// Part number (1-3), defaults to all unlocked parts
+        part: Option<Part>,
+        /// Save the description as Markdown in inputs/descriptions
+        #[clap(short, long)]
+        save: bool,
     },
     /// Solve a quest (runs the solution)
     Solve {
         /// Event/Story
         #[clap(short, long)]
-        event: Option<String>,
-        /// Quest number (1-20)
-        quest: Option<u8>,
+        event: Option<Event>,
+        /// Quest number or id, e.g. e2025-01 or e2025-01-2
+        quest: Option<QuestArg>,
         /// Part number (1-3), if provided will submit this part
-        part: Option<u8>,
+        part: Option<Part>,
+        /// Run on the example notes and compare with the expected answers instead of submitting
+        #[clap(long)]
+        example: bool,
+        /// Run each part repeatedly and report timing statistics instead of submitting
+        #[clap(short, long)]
+        bench: bool,
+        /// Fail if a part got slower than its timing at another commit
+        #[clap(short, long)]
+        compare: bool,
+        /// Give up on a part after this long, e.g. 30s
+        #[clap(long, value_parser = parse_duration)]
+        timeout: Option<Duration>,
+        /// Run the separate quest binary instead of the solution built into ec
+        #[clap(long)]
+        bin: bool,
+    },
+    /// Run every solution of an event and print a summary table
+    RunAll {
+        /// Event/Story
+        #[clap(short, long)]
+        event: Option<Event>,
+        /// Run on the example notes and compare with the expected answers
+        #[clap(long)]
+        example: bool,
+    },
+    /// Submit an answer directly, e.g. one that was computed by hand
+    Submit {
+        /// Event/Story
+        #[clap(short, long)]
+        event: Option<Event>,
+        /// Quest number or id, e.g. e2025-01
+        quest: QuestArg,
+        /// Part number (1-3)
+        part: Part,
+        /// The answer to submit
+        #[clap(required_unless_present = "from_stdin")]
+        answer: Option<String>,
+        /// Read the answer from stdin instead, e.g. for multiline answers
+        #[clap(long, conflicts_with = "answer")]
+        from_stdin: bool,
     },
 }
 
+/// A quest given as a plain number or as a (part) id
+#[derive(Debug, Clone, Copy)]
+enum QuestArg {
+    Quest(Quest),
+    Id(QuestId),
+    PartId(QuestPartId),
+}
+
+impl FromStr for QuestArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(id) = s.parse() {
+            Ok(Self::PartId(id))
+        } else if let Ok(id) = s.parse() {
+            Ok(Self::Id(id))
+        } else {
+            s.parse().map(Self::Quest)
+        }
+    }
+}
+
+/// Event, quest and part as given on the command line
+type Selection = (Option<Event>, Option<Quest>, Option<Part>);
+
+/// Combines the event, quest and part arguments, rejecting contradicting values
+fn resolve(
+    event: Option<Event>,
+    quest: Option<QuestArg>,
+    part: Option<Part>,
+) -> Result<Selection, String> {
+    let (id_event, quest, id_part) = match quest {
+        None => (None, None, None),
+        Some(QuestArg::Quest(quest)) => (None, Some(quest), None),
+        Some(QuestArg::Id(id)) => (Some(id.event), Some(id.quest), None),
+        Some(QuestArg::PartId(id)) => (Some(id.event()), Some(id.quest.quest), Some(id.part)),
+    };
+
+    let event = match (event, id_event) {
+        (Some(a), Some(b)) if a != b => return Err(format!("Conflicting events {a} and {b}")),
+        (a, b) => a.or(b),
+    };
+    let part = match (part, id_part) {
+        (Some(a), Some(b)) if a != b => return Err(format!("Conflicting parts {a} and {b}")),
+        (a, b) => a.or(b),
+    };
+    Ok((event, quest, part))
+}
+
 fn main() {
     let cli = Cli::parse();
 
     let result = match cli.command {
-        Commands::Scaffold { event, quest, part } => {
-            if let Some(p) = part
-                && !(1..=3).contains(&p)
-            {
-                eprintln!("Part must be between 1 and 3");
-                std::process::exit(1);
-            }
-            scaffold_quest(event, quest, part)
-        }
-        Commands::Solve { event, quest, part } => {
-            if let Some(p) = part
-                && !(1..=3).contains(&p)
-            {
-                eprintln!("Part must be between 1 and 3");
-                std::process::exit(1);
-            }
-            let submit = part.is_some();
-            solve_quest(event, quest, part, submit)
-        }
+        Commands::Scaffold {
+            event,
+            quest,
+            part,
+            wait,
+        } => resolve(event, quest, part)
+            .map_err(Into::into)
+            .and_then(|(event, quest, part)| scaffold_quest(event, quest, part, wait)),
+        Commands::Next { event } => print_next_unlock(event),
+        Commands::Read {
+            event,
+            quest,
+            part,
+            save,
+        } => resolve(event, quest, part)
+            .map_err(Into::into)
+            .and_then(|(event, quest, part)| read_quest(event, quest, part, save)),
+        Commands::Solve {
+            event,
+            quest,
+            part,
+            example,
+            bench,
+            compare,
+            timeout,
+            bin,
+        } => resolve(event, quest, part)
+            .map_err(Into::into)
+            .and_then(|(event, quest, part)| {
+                let options = SolveOptions {
+                    submit: part.is_some(),
+                    example,
+                    bench,
+                    compare,
+                    timeout,
+                    bin,
+                };
+                solve_quest(event, quest, part, options, &Registry::new(solutions()))
+            }),
+        Commands::RunAll { event, example } => run_all(event, example, &Registry::new(solutions())),
+        Commands::Submit {
+            event,
+            quest,
+            part,
+            answer,
+            from_stdin,
+        } => resolve(event, Some(quest), Some(part))
+            .map_err(Into::into)
+            .and_then(|(event, quest, part)| {
+                let answer = match answer {
+                    Some(answer) if !from_stdin => answer,
+                    _ => {
+                        let mut answer = String::new();
+                        std::io::stdin().read_to_string(&mut answer)?;
+                        answer
+                    }
+                };
+                submit_quest(event, quest.unwrap(), part.unwrap(), &answer)
+            }),
     };
 
     if let Err(e) = result {
         eprintln!("Error: {e}");
+        let hint = e
+            .downcast_ref::<ClientError>()
+            .and_then(ClientError::hint)
+            .or_else(|| e.downcast_ref::<SubmitError>()?.hint());
+        if let Some(hint) = hint {
+            eprintln!("Hint: {hint}");
+        }
         std::process::exit(1);
     }
 }
@@ -0,0 +1,34 @@
+mod common;
+
+use common::{NOTES, command, project_dir};
+use std::fs;
+
+#[test]
+fn test_bench_prints_summary() {
+    let dir = project_dir("bench", &[("notes.txt", NOTES)]);
+
+    let output = command(
+        env!("CARGO_BIN_EXE_quest_e2025_01"),
+        &dir,
+        &[
+            "--bench",
+            "--bench-time",
+            "20ms",
+            "--parts",
+            "1,3",
+            "--input",
+            "notes.txt",
+        ],
+    )
+    .output()
+    .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Fyrryn"), "{stdout}");
+    assert!(stdout.contains("Median"), "{stdout}");
+    assert!(stdout.contains("Total (median)"), "{stdout}");
+    assert!(!stdout.contains("Part 2"), "{stdout}");
+
+    fs::remove_dir_all(dir).unwrap();
+}
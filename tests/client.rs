@@ -0,0 +1,249 @@
+mod common;
+
+use common::{MockQuest, MockServer, SEED};
+use ec::ec::Event;
+use ec::ec::client::ClientError;
+use ec::{Part, Quest};
+use pretty_assertions::assert_eq;
+
+fn setup() -> (MockServer, Event, Quest) {
+    let server = MockServer::start();
+    server.add_quest(
+        2025,
+        1,
+        MockQuest::new(
+            ["first\nnotes", "second notes", "third notes"],
+            ["Fyrryn", "Elarzris", "Drakzyph"],
+        ),
+    );
+    (server, "e2025".parse().unwrap(), 1.try_into().unwrap())
+}
+
+#[test]
+fn test_fetch_user_seed() {
+    let (server, _, _) = setup();
+    assert_eq!(server.client().fetch_user_seed().unwrap(), SEED);
+}
+
+#[test]
+fn test_fetch_and_decrypt_input() {
+    let (server, event, quest) = setup();
+    let client = server.client();
+
+    assert_eq!(
+        client
+            .fetch_and_decrypt_input(event, quest, Part::One)
+            .unwrap(),
+        "first\nnotes"
+    );
+    assert!(
+        client
+            .fetch_and_decrypt_input(event, quest, Part::Two)
+            .is_err()
+    );
+}
+
+#[test]
+fn test_submit_answer_unlocks_next_part() {
+    let (server, event, quest) = setup();
+    let client = server.client();
+
+    let response = client
+        .submit_answer(event, quest, Part::One, "Fyrryx")
+        .unwrap();
+    assert!(!response.correct);
+    assert!(response.length_correct);
+    assert!(response.first_correct);
+
+    let response = client
+        .submit_answer(event, quest, Part::One, "Fyrryn")
+        .unwrap();
+    assert!(response.correct);
+    assert_eq!(response.global_place, Some(42));
+
+    assert_eq!(
+        client
+            .fetch_and_decrypt_input(event, quest, Part::Two)
+            .unwrap(),
+        "second notes"
+    );
+    assert_eq!(server.submissions().len(), 2);
+}
+
+#[test]
+fn test_builder_timeout() {
+    let (server, event, quest) = setup();
+    let client = ec::Client::builder()
+        .base_url(server.url())
+        .session(common::SESSION)
+        .seed(SEED)
+        .without_cache()
+        .timeout(std::time::Duration::from_secs(5))
+        .user_agent("ec-test")
+        .build()
+        .unwrap();
+
+    assert_eq!(client.seed(), SEED);
+    assert_eq!(
+        client
+            .fetch_and_decrypt_input(event, quest, Part::One)
+            .unwrap(),
+        "first\nnotes"
+    );
+}
+
+#[test]
+fn test_invalid_session() {
+    let (server, _, _) = setup();
+    let client = ec::Client::builder()
+        .base_url(server.url())
+        .session("expired")
+        .seed(SEED)
+        .without_cache()
+        .build()
+        .unwrap();
+
+    let err = client.fetch_user_seed().unwrap_err();
+    assert!(matches!(err, ClientError::SessionInvalid(401)));
+    assert!(err.hint().unwrap().contains(".ec-session"));
+}
+
+#[test]
+fn test_not_unlocked() {
+    let (server, event, quest) = setup();
+    let client = server.client();
+
+    let err = client
+        .fetch_decryption_key(event, quest, Part::Two)
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        ClientError::NotUnlocked {
+            part: Some(Part::Two),
+            ..
+        }
+    ));
+    assert_eq!(
+        err.hint().unwrap(),
+        "part 2 not unlocked yet, solve part 1 first"
+    );
+
+    let err = client
+        .fetch_encrypted_input(event, 2.try_into().unwrap(), Part::One)
+        .unwrap_err();
+    assert!(matches!(err, ClientError::NotUnlocked { part: None, .. }));
+}
+
+#[test]
+fn test_answer_cooldown() {
+    let (server, event, quest) = setup();
+    server.set_answer_cooldown(Some(60));
+
+    let err = server
+        .client()
+        .submit_answer(event, quest, Part::One, "Fyrryn")
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        ClientError::RateLimited {
+            retry_after: Some(d)
+        } if d.as_secs() == 60
+    ));
+}
+
+#[test]
+fn test_cached_inputs_work_offline() {
+    let (server, event, quest) = setup();
+    let cache_dir = common::temp_dir("cache");
+    let client = |url: String| {
+        ec::Client::builder()
+            .base_url(url)
+            .session(common::SESSION)
+            .seed(SEED)
+            .cache_dir(&cache_dir)
+            .build()
+            .unwrap()
+    };
+
+    let online = client(server.url());
+    assert_eq!(
+        online
+            .fetch_and_decrypt_input(event, quest, Part::One)
+            .unwrap(),
+        "first\nnotes"
+    );
+    online
+        .submit_answer(event, quest, Part::One, "Fyrryn")
+        .unwrap();
+    assert_eq!(
+        online
+            .fetch_and_decrypt_input(event, quest, Part::Two)
+            .unwrap(),
+        "second notes"
+    );
+
+    let url = server.url();
+    drop(server);
+    let offline = client(url);
+    assert_eq!(
+        offline
+            .fetch_and_decrypt_input(event, quest, Part::One)
+            .unwrap(),
+        "first\nnotes"
+    );
+    assert_eq!(
+        offline
+            .fetch_and_decrypt_input(event, quest, Part::Two)
+            .unwrap(),
+        "second notes"
+    );
+    assert!(
+        offline
+            .fetch_and_decrypt_input(event, quest, Part::Three)
+            .is_err()
+    );
+
+    std::fs::remove_dir_all(cache_dir).unwrap();
+}
+
+#[test]
+fn test_unwritable_cache_is_not_fatal() {
+    let (server, event, quest) = setup();
+    let cache_file = common::temp_dir("unwritable-cache").join("file");
+    std::fs::write(&cache_file, "").unwrap();
+    let client = ec::Client::builder()
+        .base_url(server.url())
+        .session(common::SESSION)
+        .seed(SEED)
+        .cache_dir(&cache_file)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        client
+            .fetch_and_decrypt_input(event, quest, Part::One)
+            .unwrap(),
+        "first\nnotes"
+    );
+    assert!(client.fetch_description(event, quest, Part::One).is_ok());
+
+    std::fs::remove_dir_all(cache_file.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn test_fetch_description() {
+    let (server, event, quest) = setup();
+    let client = server.client();
+
+    assert_eq!(
+        client.fetch_description(event, quest, Part::One).unwrap(),
+        "<h2>Part I</h2><p>Solve it.</p>"
+    );
+    assert!(matches!(
+        client.fetch_description(event, quest, Part::Two),
+        Err(ClientError::NotUnlocked {
+            part: Some(Part::Two),
+            ..
+        })
+    ));
+}
@@ -0,0 +1,405 @@
+//! In-process mock of the Everybody Codes API for integration tests.
+//!
+//! Serves `/api/user/me`, the encrypted inputs and descriptions, the quest keys and the answer endpoint
+//! from canned data, encrypting inputs the same way the real server does.
+//! Also holds the example notes and project directory helpers shared by the other tests.
+
+#![allow(dead_code)]
+
+use aes::Aes256;
+use aes::cipher::BlockEncryptMut;
+use block_padding::Pkcs7;
+use cbc::{Encryptor, cipher::KeyIvInit};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+type Aes256CbcEnc = Encryptor<Aes256>;
+
+pub const SESSION: &str = "mock-session";
+pub const SEED: u32 = 13;
+/// Example notes of quest 1 of e2025, whose answers are Fyrryn for part 1 and Elarzris for part 2
+pub const NOTES: &str = "Vyrdax,Drakzyph,Fyrryn,Elarzris\n\nR3,L2,R3,L1";
+
+/// Canned data for a single quest
+#[derive(Debug, Clone)]
+pub struct MockQuest {
+    pub inputs: [String; 3],
+    /// HTML descriptions of the parts
+    pub descriptions: [String; 3],
+    pub keys: [String; 3],
+    pub answers: [String; 3],
+    /// Number of parts whose key is handed out, increases when a part is solved
+    pub unlocked: u8,
+}
+
+impl MockQuest {
+    pub fn new(inputs: [&str; 3], answers: [&str; 3]) -> Self {
+        Self {
+            inputs: inputs.map(ToString::to_string),
+            descriptions: ["I", "II", "III"].map(|n| format!("<h2>Part {n}</h2><p>Solve it.</p>")),
+            keys: [
+                "0123456789abcdef0123456789abcdef".to_string(),
+                "fedcba9876543210fedcba9876543210".to_string(),
+                "ABCDEFGHIJKLMNOPQRSTUVWXYZ012345".to_string(),
+            ],
+            answers: answers.map(ToString::to_string),
+            unlocked: 1,
+        }
+    }
+
+    pub fn with_descriptions(mut self, descriptions: [&str; 3]) -> Self {
+        self.descriptions = descriptions.map(ToString::to_string);
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockSubmission {
+    pub event: u32,
+    pub quest: u8,
+    pub part: u8,
+    pub answer: String,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    quests: HashMap<(u32, u8), MockQuest>,
+    submissions: Vec<MockSubmission>,
+    /// When set, every answer is rejected with 429 and this `Retry-After` value
+    answer_cooldown: Option<u64>,
+}
+
+struct Request {
+    method: String,
+    path: String,
+    cookie: Option<String>,
+    body: String,
+}
+
+struct Response {
+    status: u16,
+    retry_after: Option<u64>,
+    body: String,
+}
+
+impl Response {
+    fn json(value: serde_json::Value) -> Self {
+        Self {
+            status: 200,
+            retry_after: None,
+            body: value.to_string(),
+        }
+    }
+
+    fn status(status: u16) -> Self {
+        Self {
+            status,
+            retry_after: None,
+            body: String::new(),
+        }
+    }
+}
+
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl MockServer {
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("could not bind mock server");
+        let addr = listener.local_addr().unwrap();
+        let state = Arc::new(Mutex::new(State::default()));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        {
+            let state = Arc::clone(&state);
+            let shutdown = Arc::clone(&shutdown);
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if shutdown.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    if let Ok(stream) = stream {
+                        handle_connection(stream, &state);
+                    }
+                }
+            });
+        }
+
+        Self {
+            addr,
+            state,
+            shutdown,
+        }
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    pub fn add_quest(&self, event: u32, quest: u8, data: MockQuest) {
+        self.state
+            .lock()
+            .unwrap()
+            .quests
+            .insert((event, quest), data);
+    }
+
+    pub fn set_answer_cooldown(&self, retry_after: Option<u64>) {
+        self.state.lock().unwrap().answer_cooldown = retry_after;
+    }
+
+    pub fn submissions(&self) -> Vec<MockSubmission> {
+        self.state.lock().unwrap().submissions.clone()
+    }
+
+    pub fn client(&self) -> ec::Client {
+        ec::Client::builder()
+            .base_url(self.url())
+            .session(SESSION)
+            .seed(SEED)
+            .without_cache()
+            .build()
+            .expect("could not build client")
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Wake up the accept loop so the thread can exit
+        let _ = TcpStream::connect(self.addr);
+    }
+}
+
+pub fn encrypt(plain: &str, key: &str) -> String {
+    let key_bytes = key.as_bytes();
+    let iv_bytes = &key_bytes[..16];
+
+    let len = plain.len();
+    let mut buf = plain.as_bytes().to_vec();
+    buf.resize(len + 16, 0);
+    let encrypted = Aes256CbcEnc::new(key_bytes.into(), iv_bytes.into())
+        .encrypt_padded_mut::<Pkcs7>(&mut buf, len)
+        .expect("buffer too small");
+    hex::encode(encrypted)
+}
+
+/// Creates an empty, unique working directory for a test
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("ec-test-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Creates a unique project directory for a test, marked with `.ec-root` and holding `files`
+pub fn project_dir(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = temp_dir(name);
+    std::fs::write(dir.join(".ec-root"), "").unwrap();
+    for (path, content) in files {
+        let path = dir.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+    dir
+}
+
+/// Runs `bin`, e.g. `env!("CARGO_BIN_EXE_ec")`, with `args` in `dir`
+pub fn command(bin: &str, dir: &Path, args: &[&str]) -> Command {
+    let mut command = Command::new(bin);
+    command.args(args).current_dir(dir);
+    command
+}
+
+fn handle_connection(mut stream: TcpStream, state: &Mutex<State>) {
+    let Some(request) = read_request(&stream) else {
+        return;
+    };
+    let response = route(&request, &mut state.lock().unwrap());
+
+    let reason = match response.status {
+        200 => "OK",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        429 => "Too Many Requests",
+        _ => "Error",
+    };
+    let retry_after = response
+        .retry_after
+        .map(|s| format!("Retry-After: {s}\r\n"))
+        .unwrap_or_default();
+    let _ = write!(
+        stream,
+        "HTTP/1.1 {} {reason}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n{retry_after}Connection: close\r\n\r\n{}",
+        response.status,
+        response.body.len(),
+        response.body
+    );
+}
+
+fn read_request(stream: &TcpStream) -> Option<Request> {
+    let mut reader = BufReader::new(stream);
+
+    let mut line = String::new();
+    reader.read_line(&mut line).ok()?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next()?.to_string();
+    let path = parts.next()?.to_string();
+
+    let mut cookie = None;
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header).ok()?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+
+        let (name, value) = header.split_once(':')?;
+        let value = value.trim();
+        match name.to_ascii_lowercase().as_str() {
+            "cookie" => cookie = Some(value.to_string()),
+            "content-length" => content_length = value.parse().ok()?,
+            _ => {}
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).ok()?;
+
+    Some(Request {
+        method,
+        path,
+        cookie,
+        body: String::from_utf8(body).ok()?,
+    })
+}
+
+fn route(request: &Request, state: &mut State) -> Response {
+    let authorized = request.cookie.as_deref() == Some(&format!("everybody-codes={SESSION}"));
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+
+    match (request.method.as_str(), segments.as_slice()) {
+        ("GET", ["api", "user", "me"]) => {
+            if !authorized {
+                return Response::status(401);
+            }
+            Response::json(serde_json::json!({ "seed": SEED }))
+        }
+        ("GET", ["assets", event, quest, "input", file]) => {
+            if *file != format!("{SEED}.json") {
+                return Response::status(404);
+            }
+            let Some(data) = find_quest(state, event, quest) else {
+                return Response::status(404);
+            };
+            Response::json(serde_json::json!({
+                "1": encrypt(&data.inputs[0], &data.keys[0]),
+                "2": encrypt(&data.inputs[1], &data.keys[1]),
+                "3": encrypt(&data.inputs[2], &data.keys[2]),
+            }))
+        }
+        ("GET", ["assets", event, quest, "description.json"]) => {
+            let Some(data) = find_quest(state, event, quest) else {
+                return Response::status(404);
+            };
+            Response::json(serde_json::json!({
+                "1": encrypt(&data.descriptions[0], &data.keys[0]),
+                "2": encrypt(&data.descriptions[1], &data.keys[1]),
+                "3": encrypt(&data.descriptions[2], &data.keys[2]),
+            }))
+        }
+        ("GET", ["api", "event", event, "quest", quest]) => {
+            if !authorized {
+                return Response::status(401);
+            }
+            let Some(data) = find_quest(state, event, quest) else {
+                return Response::status(404);
+            };
+            let mut keys = serde_json::Map::new();
+            for part in 1..=data.unlocked {
+                keys.insert(
+                    format!("key{part}"),
+                    data.keys[part as usize - 1].clone().into(),
+                );
+            }
+            Response::json(keys.into())
+        }
+        (
+            "POST",
+            [
+                "api",
+                "event",
+                event,
+                "quest",
+                quest,
+                "part",
+                part,
+                "answer",
+            ],
+        ) => {
+            if !authorized {
+                return Response::status(401);
+            }
+            if let Some(retry_after) = state.answer_cooldown {
+                return Response {
+                    retry_after: Some(retry_after),
+                    ..Response::status(429)
+                };
+            }
+            let (Ok(event_number), Ok(quest_number), Ok(part)) =
+                (event.parse(), quest.parse(), part.parse::<u8>())
+            else {
+                return Response::status(404);
+            };
+            let Ok(payload) = serde_json::from_str::<serde_json::Value>(&request.body) else {
+                return Response::status(400);
+            };
+            let answer = payload["answer"].as_str().unwrap_or_default().to_string();
+            state.submissions.push(MockSubmission {
+                event: event_number,
+                quest: quest_number,
+                part,
+                answer: answer.clone(),
+            });
+
+            let Some(data) = state.quests.get_mut(&(event_number, quest_number)) else {
+                return Response::status(404);
+            };
+            if !(1..=data.unlocked).contains(&part) {
+                return Response::status(404);
+            }
+            let expected = &data.answers[part as usize - 1];
+            let correct = answer == *expected;
+            if correct && part == data.unlocked && part < 3 {
+                data.unlocked += 1;
+            }
+            Response::json(serde_json::json!({
+                "correct": correct,
+                "lengthCorrect": answer.len() == expected.len(),
+                "firstCorrect": answer.chars().next() == expected.chars().next(),
+                "time": if correct { 1234 } else { 0 },
+                "localPlace": if correct { 1 } else { 0 },
+                "globalPlace": if correct { 42 } else { 0 },
+                "globalScore": if correct { 100 } else { 0 },
+            }))
+        }
+        _ => Response::status(404),
+    }
+}
+
+fn find_quest<'a>(state: &'a State, event: &str, quest: &str) -> Option<&'a MockQuest> {
+    state
+        .quests
+        .get(&(event.parse().ok()?, quest.parse().ok()?))
+}
@@ -0,0 +1,36 @@
+mod common;
+
+use common::{NOTES, command, project_dir};
+use std::fs;
+
+#[test]
+fn test_example_mode_compares_with_expected_answers() {
+    let dir = project_dir(
+        "example",
+        &[
+            ("inputs/examples/e2025-01-1.txt", NOTES),
+            ("inputs/examples/e2025-01-2.txt", NOTES),
+            ("inputs/examples/e2025-01-3.txt", NOTES),
+            ("inputs/examples/e2025-01-1.expected.txt", "Fyrryn\n"),
+            ("inputs/examples/e2025-01-2.expected.txt", "Vyrdax"),
+        ],
+    );
+
+    let output = command(
+        env!("CARGO_BIN_EXE_quest_e2025_01"),
+        &dir,
+        &["--example", "--submit", "1"],
+    )
+    .output()
+    .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<_> = stdout.lines().collect();
+    assert!(lines[0].contains("as expected"), "{stdout}");
+    assert!(lines[0].contains("not submitted"), "{stdout}");
+    assert!(lines[1].contains("expected \x1b[1mVyrdax"), "{stdout}");
+    assert!(!lines[2].contains("expected"), "{stdout}");
+
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use common::{NOTES, command, project_dir};
+use std::fs;
+
+#[test]
+fn test_missing_input_does_not_stop_other_parts() {
+    let dir = project_dir("missing-input", &[("notes.txt", NOTES)]);
+
+    let output = command(
+        env!("CARGO_BIN_EXE_quest_e2025_01"),
+        &dir,
+        &["--input", "2=notes.txt"],
+    )
+    .output()
+    .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<_> = stdout.lines().collect();
+    assert_eq!(lines[0], "Part 1: - (input missing)");
+    assert!(lines[1].contains("Elarzris"), "{stdout}");
+    assert_eq!(lines[2], "Part 3: - (input missing)");
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("cargo scaffold e2025-01-3"),
+        "{stdout}"
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_inputs_are_found_from_subdirectories() {
+    let dir = project_dir("root", &[("private/notes/e2025-01-1.txt", NOTES)]);
+    fs::create_dir_all(dir.join("src/bin")).unwrap();
+
+    let output = command(
+        env!("CARGO_BIN_EXE_quest_e2025_01"),
+        &dir.join("src/bin"),
+        &["--parts", "1"],
+    )
+    .env("EC_INPUTS_DIR", "private")
+    .output()
+    .unwrap();
+    assert!(output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stdout).contains("Fyrryn"),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+}
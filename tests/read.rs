@@ -0,0 +1,40 @@
+mod common;
+
+use common::{MockQuest, MockServer, SESSION, temp_dir};
+use pretty_assertions::assert_eq;
+use std::fs;
+use std::process::Command;
+
+#[test]
+fn test_read_saves_markdown() {
+    let server = MockServer::start();
+    server.add_quest(
+        2025,
+        4,
+        MockQuest::new(["a", "b", "c"], ["1", "2", "3"]).with_descriptions([
+            "<h2>Part I</h2><p>Count the <em>gears</em>:</p><pre class=\"note\">1\n2</pre>",
+            "",
+            "",
+        ]),
+    );
+
+    let dir = temp_dir("read");
+    fs::write(dir.join(".ec-session"), SESSION).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_ec"))
+        .args(["read", "--event", "e2025", "--save", "4"])
+        .current_dir(&dir)
+        .env("EC_BASE_URL", server.url())
+        .env("EC_SEED", common::SEED.to_string())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Count the"));
+
+    assert_eq!(
+        fs::read_to_string(dir.join("inputs/descriptions/e2025-04.md")).unwrap(),
+        "## Part I\n\nCount the *gears*:\n\n```\n1\n2\n```\n"
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+}
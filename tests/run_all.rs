@@ -0,0 +1,104 @@
+mod common;
+
+use common::{MockServer, NOTES, SEED, SESSION, command, project_dir};
+use std::fs;
+
+#[test]
+fn test_run_all_checks_expected_answers() {
+    let dir = project_dir(
+        "run-all",
+        &[
+            ("inputs/examples/e2025-01-1.txt", NOTES),
+            ("inputs/examples/e2025-01-1.expected.txt", "Fyrryn"),
+            ("inputs/examples/e2025-01-3.txt", NOTES),
+            ("inputs/examples/e2025-01-3.expected.txt", "Vyrdax"),
+        ],
+    );
+
+    let output = command(
+        env!("CARGO_BIN_EXE_ec"),
+        &dir,
+        &["run-all", "--event", "e2025", "--example"],
+    )
+    .output()
+    .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success(), "{stdout}");
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("1 of 6 parts failed"),
+        "{stdout}"
+    );
+
+    let row = |quest: &str, part: &str| {
+        stdout
+            .lines()
+            .find(|l| l.starts_with(quest) && l.contains(&format!("|    {part} |")))
+            .unwrap_or_else(|| panic!("no row for {quest}-{part} in {stdout}"))
+            .to_string()
+    };
+    assert!(row("e2025-01", "1").contains("correct"));
+    assert!(row("e2025-01", "2").contains("no input"));
+    assert!(row("e2025-01", "3").contains("wrong, expected Vyrdax"));
+    assert!(row("e2025-02", "1").contains("no input"));
+    assert!(stdout.contains("Total: "));
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_run_all_reports_a_missing_seed() {
+    let server = MockServer::start();
+    let dir = project_dir("run-all-seed", &[("inputs/notes/e2025-01-1.txt", NOTES)]);
+
+    let run_all = || {
+        let output = command(
+            env!("CARGO_BIN_EXE_ec"),
+            &dir,
+            &["run-all", "--event", "e2025"],
+        )
+        .env("HOME", &dir)
+        .env("EC_BASE_URL", server.url())
+        .env("EC_SEED", "")
+        .output()
+        .unwrap();
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    let stdout = run_all();
+    assert!(stdout.contains("answers are not checked"), "{stdout}");
+    assert!(stdout.contains("solved, unchecked"), "{stdout}");
+
+    // With a session, the seed is fetched like for any other command
+    fs::write(dir.join(".ec-session"), SESSION).unwrap();
+    let stdout = run_all();
+    assert!(stdout.contains("Fetched your seed"), "{stdout}");
+    assert!(!stdout.contains("unchecked"), "{stdout}");
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_run_all_uses_the_configured_seed_without_a_session() {
+    let dir = project_dir(
+        "run-all-configured-seed",
+        &[("inputs/notes/e2025-01-1.txt", NOTES)],
+    );
+
+    let output = command(
+        env!("CARGO_BIN_EXE_ec"),
+        &dir,
+        &["run-all", "--event", "e2025"],
+    )
+    .env("HOME", &dir)
+    // Nothing listens there, any request would fail
+    .env("EC_BASE_URL", "http://127.0.0.1:9")
+    .env("EC_SEED", format!(" {SEED}\n"))
+    .output()
+    .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("No seed"), "{stdout}");
+    assert!(!stdout.contains("unchecked"), "{stdout}");
+    assert!(stdout.contains("Fyrryn"), "{stdout}");
+
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use common::{MockQuest, MockServer, SESSION, temp_dir};
+use pretty_assertions::assert_eq;
+use std::fs;
+use std::process::Command;
+
+#[test]
+fn test_scaffold_downloads_input() {
+    let server = MockServer::start();
+    server.add_quest(
+        2025,
+        3,
+        MockQuest::new(["a,b\n\nR1", "", ""], ["b", "", ""]).with_descriptions([
+            "<p>Example:</p><pre class=\"note\">x,y,z\n\nR2</pre><p>The result is the name <code>z</code>.</p>",
+            "",
+            "",
+        ]),
+    );
+
+    let dir = temp_dir("scaffold");
+    fs::write(dir.join(".ec-session"), SESSION).unwrap();
+
+    let status = Command::new(env!("CARGO_BIN_EXE_ec"))
+        .args(["scaffold", "--event", "e2025", "3", "1"])
+        .current_dir(&dir)
+        .env("EC_BASE_URL", server.url())
+        .env("EC_SEED", common::SEED.to_string())
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    assert_eq!(
+        fs::read_to_string(dir.join("inputs/notes/e2025-03-1.txt")).unwrap(),
+        "a,b\n\nR1"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("inputs/examples/e2025-03-1.txt")).unwrap(),
+        "x,y,z\n\nR2"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("inputs/examples/e2025-03-1.expected.txt")).unwrap(),
+        "z"
+    );
+    let quest_file = fs::read_to_string(dir.join("src/bin/quest_e2025_03.rs")).unwrap();
+    assert!(quest_file.starts_with("ec::solution!(\"e2025\", 3);"));
+    assert!(quest_file.contains(
+        "    #[test]\n    fn test_part_one() {\n        let notes = read_example_file(EVENT, QUEST, Part::One);\n        let result = part_one(&notes).unpack().unwrap();\n        assert_eq!(result, \"z\");"
+    ));
+    assert!(quest_file.contains("    #[test]\n    #[ignore]\n    fn test_part_two()"));
+
+    fs::remove_dir_all(dir).unwrap();
+}
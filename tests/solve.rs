@@ -0,0 +1,28 @@
+mod common;
+
+use common::{NOTES, command, project_dir};
+use std::fs;
+
+#[test]
+fn test_ec_solves_registered_quests_in_process() {
+    // Without a Cargo.toml, spawning the quest binary with cargo would fail
+    let dir = project_dir("in-process", &[("inputs/examples/e2025-01-2.txt", NOTES)]);
+
+    let output = command(
+        env!("CARGO_BIN_EXE_ec"),
+        &dir,
+        &["solve", "--example", "e2025-01-2"],
+    )
+    .output()
+    .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Part 2: \x1b[1mElarzris"), "{stdout}");
+    assert!(!stdout.contains("Part 1"), "{stdout}");
+
+    fs::remove_dir_all(dir).unwrap();
+}
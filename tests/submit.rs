@@ -0,0 +1,63 @@
+mod common;
+
+use common::{MockQuest, MockServer, SESSION, temp_dir};
+use pretty_assertions::assert_eq;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+fn submit(server: &MockServer, dir: &Path, args: &[&str], stdin: Option<&str>) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_ec"))
+        .arg("submit")
+        .args(args)
+        .current_dir(dir)
+        .env("EC_BASE_URL", server.url())
+        .env("EC_SEED", common::SEED.to_string())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut pipe = child.stdin.take().unwrap();
+    pipe.write_all(stdin.unwrap_or_default().as_bytes())
+        .unwrap();
+    drop(pipe);
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn test_submit_uses_ledger() {
+    let server = MockServer::start();
+    server.add_quest(2025, 4, MockQuest::new(["", "", ""], ["42", "a\nb", ""]));
+
+    let dir = temp_dir("submit");
+    fs::write(dir.join(".ec-session"), SESSION).unwrap();
+
+    let output = submit(&server, &dir, &["--event", "e2025", "4", "1", "41"], None);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Incorrect answer"));
+
+    // The same wrong answer is refused without asking the server again
+    let output = submit(&server, &dir, &["e2025-04", "1", "41"], None);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("already submitted and is wrong"));
+    assert_eq!(server.submissions().len(), 1);
+
+    let output = submit(&server, &dir, &["e2025-04", "1", "42"], None);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Correct answer"));
+
+    let output = submit(
+        &server,
+        &dir,
+        &["e2025-04", "2", "--from-stdin"],
+        Some("a\nb\n"),
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Correct answer"));
+    assert_eq!(server.submissions()[2].answer, "a\nb");
+
+    let ledger = fs::read_to_string(dir.join("inputs/submissions.json")).unwrap();
+    assert_eq!(ledger.matches("\"answer\"").count(), 3);
+
+    fs::remove_dir_all(dir).unwrap();
+}
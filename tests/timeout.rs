@@ -0,0 +1,40 @@
+mod common;
+
+use clap::Parser;
+use common::temp_dir;
+use ec::RunConfig;
+use ec::ec::solution::{PartStatus, Solution};
+use std::fs;
+use std::time::Duration;
+
+mod looping {
+    use std::time::Duration;
+
+    ec::solution!("e2025", 1, 1, timeout = Duration::from_millis(100));
+
+    fn part_one(notes: &str) -> Option<usize> {
+        loop {
+            std::thread::sleep(Duration::from_millis(10));
+            if notes.is_empty() {
+                return Some(0);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_parts_time_out() {
+    let dir = temp_dir("timeout");
+    let notes = dir.join("notes.txt");
+    fs::write(&notes, "A,B\n\nR1").unwrap();
+
+    let config = RunConfig::parse_from(["quest", "--input", notes.to_str().unwrap()]);
+    let outcome = looping::QuestSolution.run(&config);
+    assert_eq!(
+        outcome.parts[0].status,
+        PartStatus::TimedOut(Duration::from_millis(100))
+    );
+    assert!(outcome.aborted());
+
+    fs::remove_dir_all(dir).unwrap();
+}
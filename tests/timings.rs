@@ -0,0 +1,43 @@
+mod common;
+
+use common::{command, project_dir};
+use std::fs;
+
+#[test]
+fn test_compare_fails_on_regression() {
+    // Long enough that running it takes way more than the regression noise floor
+    let notes = format!("Vyrdax,Drakzyph\n\n{}", vec!["R1"; 200_000].join(","));
+    let dir = project_dir("compare", &[("inputs/notes/e2025-01-1.txt", &notes)]);
+
+    let baseline = |seconds: f64| {
+        let records = serde_json::json!([{
+            "event": "e2025", "quest": 1, "part": 1, "commit": "old",
+            "bench": false, "seconds": seconds, "timestamp": 0,
+        }]);
+        fs::write(dir.join("inputs/timings.json"), records.to_string()).unwrap();
+    };
+    let compare = || {
+        command(
+            env!("CARGO_BIN_EXE_quest_e2025_01"),
+            &dir,
+            &["--parts", "1", "--compare", "--baseline", "old"],
+        )
+        .output()
+        .unwrap()
+    };
+
+    baseline(10.0);
+    let output = compare();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("at old"));
+
+    baseline(1e-9);
+    let output = compare();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("slower"));
+
+    let timings = fs::read_to_string(dir.join("inputs/timings.json")).unwrap();
+    assert!(timings.contains("\"commit\": \"unknown\""), "{timings}");
+
+    fs::remove_dir_all(dir).unwrap();
+}
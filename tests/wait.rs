@@ -0,0 +1,73 @@
+mod common;
+
+use common::{MockQuest, MockServer, SESSION, temp_dir};
+use ec::ec::schedule::Clock;
+use ec::scaffold_quest_with_clock;
+use pretty_assertions::assert_eq;
+use std::cell::{Cell, RefCell};
+use std::fs;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Clock that starts an hour before e2025 quest 2 unlocks and releases the quest on the second retry
+struct FakeClock<'a> {
+    now: Cell<SystemTime>,
+    sleeps: RefCell<Vec<Duration>>,
+    server: &'a MockServer,
+}
+
+impl Clock for FakeClock<'_> {
+    fn now(&self) -> SystemTime {
+        self.now.get()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.now.set(self.now.get() + duration);
+        let mut sleeps = self.sleeps.borrow_mut();
+        sleeps.push(duration);
+        if sleeps.len() == 3 {
+            self.server
+                .add_quest(2025, 2, MockQuest::new(["late", "", ""], ["", "", ""]));
+        }
+    }
+}
+
+#[test]
+fn test_scaffold_waits_for_unlock() {
+    let server = MockServer::start();
+    let dir = temp_dir("wait");
+    fs::write(dir.join(".ec-session"), SESSION).unwrap();
+
+    // This is the only test in this binary, so changing the process environment is fine
+    std::env::set_current_dir(&dir).unwrap();
+    unsafe {
+        std::env::set_var("EC_BASE_URL", server.url());
+        std::env::set_var("EC_SEED", common::SEED.to_string());
+    }
+
+    // e2025 quest 2 unlocks on 2025-11-04 23:00 UTC
+    let unlock = UNIX_EPOCH + Duration::from_secs(1762297200);
+    let clock = FakeClock {
+        now: Cell::new(unlock - Duration::from_secs(3600)),
+        sleeps: RefCell::new(Vec::new()),
+        server: &server,
+    };
+
+    scaffold_quest_with_clock(
+        Some("e2025".parse().unwrap()),
+        Some(2.try_into().unwrap()),
+        Some(ec::Part::One),
+        Some(&clock),
+    )
+    .unwrap();
+
+    assert_eq!(
+        *clock.sleeps.borrow(),
+        [3600, 1, 2].map(Duration::from_secs)
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("inputs/notes/e2025-02-1.txt")).unwrap(),
+        "late"
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+}